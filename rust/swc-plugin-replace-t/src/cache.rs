@@ -1,26 +1,77 @@
 use std::{
-    collections::HashMap,
-    fmt,
-    fs::{self, File},
-    io::{self, BufReader},
-    path::PathBuf,
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, HashSet},
+    fmt, fs, io,
+    path::{Path, PathBuf},
     time::SystemTime,
 };
 
-use jsonpath_rust::JsonPathQuery;
-use serde_json::Value;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    catalog::{CatalogFormat, ParseError},
+    config::Config,
+    nesting::{find_nestings, interpolate_options, option_placeholder, parse_options},
+    plural::{plural_category, PLURAL_SUFFIXES},
+};
 
 pub struct Cache {
     base_path: PathBuf,
-    map: HashMap<String, Value>,
+    locale: String,
+    fallback_locales: Vec<String>,
+    path: String,
+    format: CatalogFormat,
+    ns_separator: Option<String>,
+    key_separator: Option<String>,
+    /// catalogs keyed by locale and namespace e.g) ("en", "common")
+    map: HashMap<(String, String), Value>,
     mtimes: HashMap<String, SystemTime>,
+    fallbacks: RefCell<Vec<Fallback>>,
+    /// fallbacks already taken so that each key is reported once
+    reported: RefCell<HashSet<Fallback>>,
+}
+
+/// A key served from a fallback locale as it is missing in the locale
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Fallback {
+    pub locale: String,
+    pub namespace: String,
+    pub key: String,
+}
+
+/// Writes fallbacks of the locale to the report as JSON lines merged with the lines
+/// already reported so that rebuilds don't duplicate them
+/// e.g) {"from":"en","key":"farewell","locale":"de","namespace":"common"}
+pub fn write_fallbacks(path: &Path, locale: &str, fallbacks: &[Fallback]) -> io::Result<()> {
+    let mut lines = match fs::read_to_string(path) {
+        Ok(content) => content.lines().map(str::to_string).collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+        Err(e) => return Err(e),
+    };
+    for fallback in fallbacks {
+        let line = json!({
+            "locale": locale,
+            "namespace": fallback.namespace,
+            "key": fallback.key,
+            "from": fallback.locale,
+        });
+        lines.insert(line.to_string());
+    }
+    let content: String = lines.into_iter().map(|line| line + "\n").collect();
+    fs::write(path, content)
 }
 
 #[derive(Debug)]
 pub enum CacheError {
     IoError(io::Error),
     JsonError(serde_json::Error),
+    ParseError(String, ParseError),
     KeyNotFound(String),
+    CircularNesting(Vec<String>),
+    /// options of a nested translation are not valid e.g) $t(key, {count: 1})
+    NestingOptions(String, String),
+    /// plural rules of the locale are not known at compile time
+    UnknownPluralRules(String),
 }
 
 impl From<io::Error> for CacheError {
@@ -39,59 +90,349 @@ impl fmt::Display for CacheError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::JsonError(e) => write!(f, "cannot read a content from json file. {}", e),
-            Self::IoError(e) => write!(f, "cannot read a json file. {}", e),
+            Self::IoError(e) => write!(f, "cannot read a catalog. {}", e),
+            Self::ParseError(path, e) => write!(f, "cannot parse a catalog. {}:{}", path, e),
             Self::KeyNotFound(key) => write!(f, "key does not found. key: {}", key),
+            Self::CircularNesting(keys) => {
+                write!(f, "circular nesting detected. {}", keys.join(" -> "))
+            }
+            Self::NestingOptions(key, e) => {
+                write!(f, "cannot parse options of a nesting. key: {}. {}", key, e)
+            }
+            Self::UnknownPluralRules(locale) => {
+                write!(f, "plural rules are unknown. locale: {}", locale)
+            }
         }
     }
 }
 
 impl Cache {
-    pub fn new(base_path: String) -> Self {
+    pub fn new(config: &Config) -> Self {
         Self {
-            base_path: PathBuf::from(base_path),
+            base_path: PathBuf::from(&config.base_dir),
+            locale: config.locale.clone(),
+            fallback_locales: config.fallback_locales.clone(),
+            path: config.path.clone(),
+            format: config
+                .format
+                .or_else(|| CatalogFormat::from_path(&config.path))
+                .unwrap_or(CatalogFormat::Json),
+            ns_separator: config.ns_separator.clone(),
+            key_separator: config.key_separator.clone(),
             map: HashMap::new(),
             mtimes: HashMap::new(),
+            fallbacks: RefCell::new(vec![]),
+            reported: RefCell::new(HashSet::new()),
         }
     }
 
+    /// Switches the locale to look up e.g) useTranslation("common", { lng: "de" })
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = locale.to_string();
+    }
+
+    /// Returns the locale followed by fallback locales e.g) ["de", "en"]
+    fn locales(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.locale).chain(self.fallback_locales.iter())
+    }
+
+    /// Loads catalogs of the namespace in the locale and its fallback locales
     pub fn add_file(&mut self, scope: String) -> Result<(), CacheError> {
-        let path = PathBuf::from("/cwd")
-            .join(&self.base_path)
-            .join(format!("{}.json", scope));
+        let locales: Vec<String> = self.locales().cloned().collect();
+        let mut loaded = false;
+        let mut error = None;
+        let mut namespaces = vec![];
+        for locale in locales {
+            match self.load_file(&locale, &scope) {
+                Ok(nested) => {
+                    loaded = true;
+                    namespaces.extend(nested);
+                }
+                // a catalog missing in some of locales is served from the others
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        if !loaded {
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+
+        for namespace in namespaces {
+            if !self.map.keys().any(|(_, ns)| *ns == namespace) {
+                // a missing namespace is reported when the nested key is resolved
+                let _ = self.add_file(namespace);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads a catalog and returns namespaces referred by its nested translations
+    fn load_file(&mut self, locale: &str, scope: &str) -> Result<Vec<String>, CacheError> {
+        // e.g) {baseDir}/{locale}/{ns}.json -> /cwd/locales/en/common.json
+        let path = PathBuf::from("/cwd").join(
+            self.path
+                .replace("{baseDir}", self.base_path.to_str().unwrap())
+                .replace("{locale}", locale)
+                .replace("{ns}", scope),
+        );
         let path_string = path.to_str().unwrap().to_string();
         let modified = fs::metadata(&path)?.modified()?;
+        let id = (locale.to_string(), scope.to_string());
 
         if let Some(mtime) = self.mtimes.get(&path_string) {
-            if *mtime == modified {
-                return Ok(());
+            if *mtime == modified && self.map.contains_key(&id) {
+                return Ok(vec![]);
             }
         }
 
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let json = serde_json::from_reader(reader)?;
+        let content = fs::read_to_string(path)?;
+        let json = self
+            .format
+            .parse(&content, locale)
+            .map_err(|e| CacheError::ParseError(path_string.clone(), e))?;
+
+        // load namespaces referred by nested translations e.g) $t(common:appName)
+        let mut namespaces = vec![];
+        collect_nested_namespaces(&json, self.ns_separator.as_deref(), &mut namespaces);
 
-        self.map.insert(scope, json);
+        self.map.insert(id, json);
         self.mtimes.insert(path_string, modified);
 
-        Ok(())
+        Ok(namespaces)
     }
 
+    /// Returns a message of the key with nested translations resolved e.g) $t(appName)
     pub fn get(&self, filename: String, key: String) -> Result<String, CacheError> {
-        let Some(json) = self.map.get(&filename) else {
-            return Err(CacheError::KeyNotFound(key));
-        };
+        let value = self.get_raw(&filename, &key)?;
+        self.resolve_nestings(
+            &filename,
+            &value,
+            &mut vec![format!("{}:{}", filename, key)],
+        )
+    }
+
+    /// Drains keys served from fallback locales since the last call, each key once per run
+    pub fn take_fallbacks(&self) -> Vec<Fallback> {
+        self.fallbacks.take()
+    }
+
+    fn get_raw(&self, filename: &str, key: &str) -> Result<String, CacheError> {
+        let (value, locale) = self.lookup(filename, key)?;
+        if *locale != self.locale {
+            let fallback = Fallback {
+                locale: locale.to_string(),
+                namespace: filename.to_string(),
+                key: key.to_string(),
+            };
+            if self.reported.borrow_mut().insert(fallback.clone()) {
+                self.fallbacks.borrow_mut().push(fallback);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Finds the key through the locale and fallback locales in order
+    fn lookup(&self, filename: &str, key: &str) -> Result<(String, &String), CacheError> {
+        for locale in self.locales() {
+            let Some(json) = self.map.get(&(locale.to_string(), filename.to_string())) else {
+                continue;
+            };
+            if let Some(Value::String(value)) = self.find(json, key) {
+                return Ok((value.to_string(), locale));
+            }
+        }
+        Err(CacheError::KeyNotFound(key.to_string()))
+    }
+
+    /// Finds a value of the key split by keySeparator e.g) "foo.bar" -> { "foo": { "bar": "..." } }
+    fn find<'a>(&self, json: &'a Value, key: &str) -> Option<&'a Value> {
+        match &self.key_separator {
+            Some(separator) => key
+                .split(separator.as_str())
+                .try_fold(json, |json, key| json.get(key)),
+            None => json.get(key),
+        }
+    }
+
+    fn resolve_nestings(
+        &self,
+        filename: &str,
+        message: &str,
+        stack: &mut Vec<String>,
+    ) -> Result<String, CacheError> {
+        let mut resolved = String::new();
+        let mut last = 0;
+        for nesting in find_nestings(message) {
+            resolved.push_str(&message[last..nesting.range.start]);
+            last = nesting.range.end;
+
+            let (filename, key) = self
+                .ns_separator
+                .as_deref()
+                .and_then(|separator| nesting.key.split_once(separator))
+                .unwrap_or((filename, nesting.key));
+            let options = match nesting.options {
+                Some(options) => parse_options(options).map_err(|e| {
+                    CacheError::NestingOptions(nesting.key.to_string(), e.to_string())
+                })?,
+                None => Map::new(),
+            };
+
+            // e.g) $t(friend, {"context": "male", "count": 2}) -> friend_male_other
+            let mut key = key.to_string();
+            if let Some(Value::String(context)) = options.get("context") {
+                let context_key = format!("{}_{}", key, context);
+                if self.has_key(filename, &context_key) {
+                    key = context_key;
+                }
+            }
+            // e.g) $t(girls, {"count": {{girls}}}) is pluralized at runtime
+            if options.get("count").and_then(option_placeholder).is_some() {
+                let key = match &self.ns_separator {
+                    Some(separator) => format!("{}{}{}", filename, separator, key),
+                    None => key,
+                };
+                resolved.push_str(&format!("$t({}, {})", key, Value::Object(options)));
+                continue;
+            }
+            if let Some(Value::Number(count)) = options.get("count") {
+                let plurals = self.get_plurals(filename, &key)?;
+                // JSON numbers have no visible fraction digits in JS e.g) 1.0 -> "1"
+                let count = count.as_f64().unwrap_or_default().to_string();
+                let has_zero = plurals.iter().any(|(suffix, _)| *suffix == "zero");
+                let category = if has_zero && count.parse::<f64>() == Ok(0.0) {
+                    "zero"
+                } else {
+                    plural_category(&self.locale, &count)
+                        .ok_or_else(|| CacheError::UnknownPluralRules(self.locale.clone()))?
+                };
+                let suffix = [category, "other"]
+                    .into_iter()
+                    .find(|category| plurals.iter().any(|(suffix, _)| suffix == category));
+                if let Some(suffix) = suffix {
+                    key = format!("{}_{}", key, suffix);
+                }
+            }
+
+            let id = format!("{}:{}", filename, key);
+            if stack.contains(&id) {
+                stack.push(id);
+                return Err(CacheError::CircularNesting(stack.clone()));
+            }
+            stack.push(id);
+            let value = self.get_raw(filename, &key)?;
+            let value = self.resolve_nestings(filename, &value, stack)?;
+            stack.pop();
+
+            // interpolate options of the nested translation e.g) {"name": "foo"}
+            resolved.push_str(&interpolate_options(&value, &options));
+        }
+        resolved.push_str(&message[last..]);
+
+        Ok(resolved)
+    }
+
+    /// Returns plural variants of the key e.g) foo_one, foo_other
+    pub fn get_plurals(
+        &self,
+        filename: &str,
+        key: &str,
+    ) -> Result<Vec<(&'static str, String)>, CacheError> {
+        let mut plurals = vec![];
+        for suffix in PLURAL_SUFFIXES {
+            match self.get(filename.to_string(), format!("{}_{}", key, suffix)) {
+                Ok(value) => plurals.push((suffix, value)),
+                Err(CacheError::KeyNotFound(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(plurals)
+    }
 
-        let key = format!("$.{}", key.as_str());
-        let Ok(Value::Array(array)) = json.clone().path(&key) else {
-            return Err(CacheError::KeyNotFound(key));
+    /// Returns contexts of the key e.g) foo_male, foo_female_one -> ["female", "male"]
+    pub fn get_contexts(&self, filename: &str, key: &str) -> Vec<String> {
+        let (parent, name) = match self
+            .key_separator
+            .as_deref()
+            .and_then(|separator| key.rsplit_once(separator))
+        {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, key),
         };
+        let prefix = format!("{}_", name);
+        let mut contexts: Vec<String> = vec![];
+        for locale in self.locales() {
+            let Some(json) = self.map.get(&(locale.to_string(), filename.to_string())) else {
+                continue;
+            };
+            let object = match parent {
+                Some(parent) => self.find(json, parent),
+                None => Some(json),
+            };
+            let Some(Value::Object(object)) = object else {
+                continue;
+            };
 
-        if let Some(value) = array.get(0) {
-            Ok(value.as_str().unwrap().to_string())
-        } else {
-            Err(CacheError::KeyNotFound(key))
+            for key in object.keys() {
+                let Some(suffix) = key.strip_prefix(&prefix) else {
+                    continue;
+                };
+                // e.g) female_one -> female
+                let context = PLURAL_SUFFIXES
+                    .iter()
+                    .find_map(|plural| suffix.strip_suffix(&format!("_{}", plural)))
+                    .unwrap_or(suffix);
+                if PLURAL_SUFFIXES.contains(&context) || contexts.iter().any(|c| c == context) {
+                    continue;
+                }
+                contexts.push(context.to_string());
+            }
         }
+        contexts
+    }
+
+    /// Returns true if the key or its plural variants exist
+    pub fn has_key(&self, filename: &str, key: &str) -> bool {
+        let has_plurals = PLURAL_SUFFIXES.iter().any(|suffix| {
+            self.lookup(filename, &format!("{}_{}", key, suffix))
+                .is_ok()
+        });
+        self.lookup(filename, key).is_ok() || has_plurals
+    }
+}
+
+/// Collects namespaces of nested translations e.g) $t(common:appName) -> common
+fn collect_nested_namespaces(
+    json: &Value,
+    ns_separator: Option<&str>,
+    namespaces: &mut Vec<String>,
+) {
+    match json {
+        Value::String(message) => {
+            let Some(separator) = ns_separator else {
+                return;
+            };
+            for nesting in find_nestings(message) {
+                if let Some((namespace, _)) = nesting.key.split_once(separator) {
+                    namespaces.push(namespace.to_string());
+                }
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values() {
+                collect_nested_namespaces(value, ns_separator, namespaces);
+            }
+        }
+        Value::Array(array) => {
+            for value in array {
+                collect_nested_namespaces(value, ns_separator, namespaces);
+            }
+        }
+        _ => {}
     }
 }
 
@@ -101,16 +442,20 @@ mod tests {
 
     use super::*;
 
+    /// Returns the absolute path of a fixture directory e.g) fixture/json
+    fn fixture_dir(name: &str) -> String {
+        let path = current_dir().unwrap().join("fixture").join(name);
+        path.to_str().unwrap().to_string()
+    }
+
     #[test]
     fn test_cache() {
-        let base_path = current_dir()
-            .unwrap()
-            .join(PathBuf::from("fixture/json"))
-            .to_str()
-            .unwrap()
-            .to_string();
+        let base_path = fixture_dir("json");
 
-        let mut cache = Cache::new(base_path);
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            ..Default::default()
+        });
 
         // Test adding file to cache
         assert!(cache.add_file("noArgs".to_string()).is_ok());
@@ -129,4 +474,180 @@ mod tests {
         // Test getting value from non-existent file
         assert!(cache.get("notExists".to_string(), "a".to_string()).is_err());
     }
+
+    #[test]
+    fn test_get_plurals() {
+        let base_path = fixture_dir("json");
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            ..Default::default()
+        });
+        assert!(cache.add_file("plural".to_string()).is_ok());
+
+        assert_eq!(
+            cache.get_plurals("plural", "item").unwrap(),
+            vec![
+                ("one", "{{count}} item".to_string()),
+                ("other", "{{count}} items".to_string())
+            ]
+        );
+        assert!(cache.get_plurals("plural", "notExists").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_contexts() {
+        let base_path = fixture_dir("json");
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            ..Default::default()
+        });
+        assert!(cache.add_file("context".to_string()).is_ok());
+
+        assert_eq!(
+            cache.get_contexts("context", "friend"),
+            vec!["female", "male"]
+        );
+        assert_eq!(
+            cache.get_contexts("context", "nested.title"),
+            vec!["formal"]
+        );
+        assert!(cache.has_key("context", "friend_male"));
+        assert!(cache.has_key("context", "friend"));
+        assert!(!cache.has_key("context", "friend_other"));
+    }
+
+    #[test]
+    fn test_nesting() {
+        let base_path = fixture_dir("json");
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            ..Default::default()
+        });
+        assert!(cache.add_file("nesting".to_string()).is_ok());
+
+        let get = |key: &str| cache.get("nesting".to_string(), key.to_string());
+        assert_eq!(get("welcome").unwrap(), "Welcome to Sandbox");
+        // noArgs is loaded with nesting.json
+        assert_eq!(get("cross").unwrap(), "test1 and test2");
+        assert_eq!(get("cart").unwrap(), "You have 3 items");
+        assert_eq!(get("greetUser").unwrap(), "Hello {{user}}!");
+        assert_eq!(get("partner").unwrap(), "with A boyfriend");
+        assert_eq!(get("twoGirls").unwrap(), "2 girls");
+        assert_eq!(
+            get("girls").unwrap(),
+            r#"$t(nesting:girl, {"count":"{{girls}}"}) in {{city}}"#
+        );
+        assert!(matches!(
+            get("badOptions"),
+            Err(CacheError::NestingOptions(key, _)) if key == "girl"
+        ));
+        assert!(matches!(
+            get("loopA"),
+            Err(CacheError::CircularNesting(keys)) if keys == vec![
+                "nesting:loopA",
+                "nesting:loopB",
+                "nesting:loopA",
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_path() {
+        let base_path = fixture_dir("locales");
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            locale: "de".to_string(),
+            path: "{baseDir}/{locale}/{ns}.json".to_string(),
+            ..Default::default()
+        });
+        assert!(cache.add_file("common".to_string()).is_ok());
+        assert_eq!(
+            cache
+                .get("common".to_string(), "greeting".to_string())
+                .unwrap(),
+            "Hallo"
+        );
+    }
+
+    #[test]
+    fn test_fallback_locales() {
+        let base_path = fixture_dir("locales");
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            locale: "de-AT".to_string(),
+            fallback_locales: vec!["de".to_string(), "en".to_string()],
+            path: "{baseDir}/{locale}/{ns}.json".to_string(),
+            ..Default::default()
+        });
+        // de-AT/common.json does not exist
+        assert!(cache.add_file("common".to_string()).is_ok());
+
+        let get = |key: &str| cache.get("common".to_string(), key.to_string());
+        assert_eq!(get("greeting").unwrap(), "Hallo");
+        assert_eq!(get("farewell").unwrap(), "Goodbye");
+        assert!(get("notExists").is_err());
+        assert_eq!(
+            cache.take_fallbacks(),
+            vec![
+                Fallback {
+                    locale: "de".to_string(),
+                    namespace: "common".to_string(),
+                    key: "greeting".to_string(),
+                },
+                Fallback {
+                    locale: "en".to_string(),
+                    namespace: "common".to_string(),
+                    key: "farewell".to_string(),
+                },
+            ]
+        );
+        assert!(cache.take_fallbacks().is_empty());
+
+        assert!(cache.add_file("notExists".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_formats() {
+        let base_path = fixture_dir("formats");
+
+        for path in ["{baseDir}/{ns}.yaml", "{baseDir}/{ns}.json5"] {
+            let mut cache = Cache::new(&Config {
+                base_dir: base_path.clone(),
+                path: path.to_string(),
+                ..Default::default()
+            });
+            assert!(cache.add_file("common".to_string()).is_ok());
+            let get = |key: &str| cache.get("common".to_string(), key.to_string());
+            assert_eq!(get("greeting").unwrap(), "Hello {{name}}");
+            assert_eq!(get("nested.title").unwrap(), "Title");
+            assert_eq!(get("apple_other").unwrap(), "{{count}} apples");
+        }
+
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path.clone(),
+            path: "{baseDir}/{ns}.po".to_string(),
+            ..Default::default()
+        });
+        assert!(cache.add_file("common".to_string()).is_ok());
+        let get = |key: &str| cache.get("common".to_string(), key.to_string());
+        assert_eq!(get("greeting_formal").unwrap(), "Good day {{name}}");
+        assert_eq!(get("apple_one").unwrap(), "{{count}} apple");
+
+        // the format is given by config regardless of the extension
+        let mut cache = Cache::new(&Config {
+            base_dir: base_path,
+            path: "{baseDir}/{ns}.po".to_string(),
+            format: Some(CatalogFormat::Yaml),
+            ..Default::default()
+        });
+        assert!(matches!(
+            cache.add_file("common".to_string()),
+            Err(CacheError::ParseError(path, _)) if path.ends_with("common.po")
+        ));
+    }
 }
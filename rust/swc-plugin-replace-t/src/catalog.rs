@@ -0,0 +1,455 @@
+use std::{fmt, path::Path};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::plural::plural_forms;
+
+/// A format of catalogs
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogFormat {
+    Json,
+    Json5,
+    Yaml,
+    /// gettext catalogs e.g) msgctxt "male" msgid "friend" msgstr "boyfriend"
+    Po,
+}
+
+/// An error of parsing a catalog at one-based line and column
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} {}", self.line, self.column, self.message)
+    }
+}
+
+impl CatalogFormat {
+    /// Infers the format from extension of the path e.g) "{baseDir}/{ns}.yml" -> Yaml
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "json5" => Some(Self::Json5),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "po" => Some(Self::Po),
+            _ => None,
+        }
+    }
+
+    /// Parses a catalog into json which Cache looks up
+    /// Plural forms of gettext are mapped into plural suffixes of the locale e.g) msgstr[1] -> key_other
+    pub fn parse(self, content: &str, locale: &str) -> Result<Value, ParseError> {
+        match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| ParseError {
+                line: e.line(),
+                column: e.column(),
+                message: strip_location(&e.to_string(), e.line(), e.column()),
+            }),
+            Self::Json5 => json5::from_str(content).map_err(|e| {
+                let json5::Error::Message { msg, location } = e;
+                let location = location.unwrap_or(json5::Location { line: 1, column: 1 });
+                // e.g) " --> 1:5\n  |\n1 | {\"a\"\n  |     ^---\n  |\n  = expected ..."
+                let message = msg
+                    .lines()
+                    .last()
+                    .unwrap_or_default()
+                    .trim()
+                    .trim_start_matches("= ");
+                ParseError {
+                    line: location.line,
+                    column: location.column,
+                    message: message.to_string(),
+                }
+            }),
+            Self::Yaml => serde_yaml::from_str(content).map_err(|e| {
+                let (line, column) = e
+                    .location()
+                    .map(|location| (location.line(), location.column()))
+                    .unwrap_or((1, 1));
+                ParseError {
+                    line,
+                    column,
+                    message: strip_location(&e.to_string(), line, column),
+                }
+            }),
+            Self::Po => parse_po(content, locale).map(Value::Object),
+        }
+    }
+}
+
+/// Strips a location from an error message e.g) "EOF at line 1 column 2" -> "EOF"
+fn strip_location(message: &str, line: usize, column: usize) -> String {
+    message
+        .trim_end_matches(&format!(" at line {} column {}", line, column))
+        .to_string()
+}
+
+/// An entry of gettext catalogs
+#[derive(Default)]
+struct PoEntry {
+    /// line where the entry starts
+    line: usize,
+    context: Option<String>,
+    id: Option<String>,
+    plural: Option<String>,
+    strs: Vec<(usize, String)>,
+    fuzzy: bool,
+}
+
+/// A field of an entry which continued strings are appended to
+enum PoField {
+    Context,
+    Id,
+    Plural,
+    Str,
+}
+
+/// Parses a gettext catalog into flat keys as i18next does
+/// e.g) msgctxt "male" msgid "friend" msgstr "boyfriend" -> { "friend_male": "boyfriend" }
+fn parse_po(content: &str, locale: &str) -> Result<Map<String, Value>, ParseError> {
+    // plural forms follow the locale unless the header gives nplurals
+    let mut categories = plural_forms(locale, None);
+    let mut map = Map::new();
+    let mut entry = PoEntry::default();
+    let mut field = None;
+
+    let mut flush = |entry: &mut PoEntry, map: &mut Map<String, Value>| {
+        let entry = std::mem::take(entry);
+        let Some(id) = entry.id else {
+            return Ok(());
+        };
+        // e.g) "Plural-Forms: nplurals=2; plural=(n > 1);\n"
+        if id.is_empty() {
+            if let Some((_, header)) = entry.strs.first() {
+                if let Some(nplurals) = nplurals_from_header(header) {
+                    categories = plural_forms(locale, Some(nplurals));
+                }
+            }
+            return Ok(());
+        }
+        // skip fuzzy and untranslated entries
+        if entry.fuzzy {
+            return Ok(());
+        }
+        let key = match entry.context {
+            Some(context) => format!("{}_{}", id, context),
+            None => id,
+        };
+        for (index, value) in entry.strs {
+            if value.is_empty() {
+                continue;
+            }
+            if entry.plural.is_none() {
+                map.insert(key.clone(), Value::String(value));
+                continue;
+            }
+            let Some(categories) = &categories else {
+                return Err(ParseError {
+                    line: entry.line,
+                    column: 1,
+                    message: format!("unknown plural forms of locale: {}", locale),
+                });
+            };
+            if let Some(category) = categories.get(index) {
+                map.insert(format!("{}_{}", key, category), Value::String(value));
+            }
+        }
+        Ok(())
+    };
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(comment) = trimmed.strip_prefix('#') {
+            if entry.id.is_some() {
+                flush(&mut entry, &mut map)?;
+            }
+            // e.g) #, fuzzy, python-format
+            if let Some(flags) = comment.strip_prefix(',') {
+                entry.fuzzy |= flags.split(',').any(|flag| flag.trim() == "fuzzy");
+            }
+            field = None;
+            continue;
+        }
+        if trimmed.starts_with('"') {
+            let value = parse_po_string(trimmed, line_number, indent + 1)?;
+            match field {
+                Some(PoField::Context) => entry.context.get_or_insert_with(String::new),
+                Some(PoField::Id) => entry.id.get_or_insert_with(String::new),
+                Some(PoField::Plural) => entry.plural.get_or_insert_with(String::new),
+                Some(PoField::Str) => match entry.strs.last_mut() {
+                    Some((_, value)) => value,
+                    None => unreachable!(),
+                },
+                None => {
+                    return Err(ParseError {
+                        line: line_number,
+                        column: indent + 1,
+                        message: "string without keyword".to_string(),
+                    })
+                }
+            }
+            .push_str(&value);
+            continue;
+        }
+
+        let (keyword, rest) = trimmed
+            .split_once(|c: char| c.is_whitespace())
+            .unwrap_or((trimmed, ""));
+        let rest = rest.trim_start();
+        let column = indent + trimmed.len() - rest.len() + 1;
+        let value = parse_po_string(rest, line_number, column)?;
+        match keyword {
+            "msgctxt" => {
+                if entry.id.is_some() {
+                    flush(&mut entry, &mut map)?;
+                }
+                entry.line = line_number;
+                entry.context = Some(value);
+                field = Some(PoField::Context);
+            }
+            "msgid" => {
+                if entry.id.is_some() {
+                    flush(&mut entry, &mut map)?;
+                }
+                if entry.context.is_none() {
+                    entry.line = line_number;
+                }
+                entry.id = Some(value);
+                field = Some(PoField::Id);
+            }
+            "msgid_plural" => {
+                entry.plural = Some(value);
+                field = Some(PoField::Plural);
+            }
+            "msgstr" => {
+                entry.strs.push((0, value));
+                field = Some(PoField::Str);
+            }
+            keyword => {
+                // e.g) msgstr[1]
+                let Some(index) = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                else {
+                    return Err(ParseError {
+                        line: line_number,
+                        column: indent + 1,
+                        message: format!("unknown keyword: {}", keyword),
+                    });
+                };
+                entry.strs.push((index, value));
+                field = Some(PoField::Str);
+            }
+        }
+    }
+    flush(&mut entry, &mut map)?;
+
+    Ok(map)
+}
+
+/// Returns nplurals of the Plural-Forms header e.g) "Plural-Forms: nplurals=3; plural=...;" -> 3
+fn nplurals_from_header(header: &str) -> Option<usize> {
+    let forms = header
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Plural-Forms:"))?;
+    forms.split(';').find_map(|form| {
+        let (name, value) = form.split_once('=')?;
+        (name.trim() == "nplurals")
+            .then(|| value.trim().parse().ok())
+            .flatten()
+    })
+}
+
+/// Parses a quoted string of gettext catalogs e.g) "foo\n" -> foo + newline
+fn parse_po_string(value: &str, line: usize, column: usize) -> Result<String, ParseError> {
+    let error = |offset: usize, message: &str| ParseError {
+        line,
+        column: column + offset,
+        message: message.to_string(),
+    };
+    let Some(body) = value.strip_prefix('"') else {
+        return Err(error(0, "expected a quoted string"));
+    };
+
+    let mut result = String::new();
+    let mut chars = body.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                return if body[i + 1..].trim().is_empty() {
+                    Ok(result)
+                } else {
+                    Err(error(i + 2, "unexpected characters after a string"))
+                };
+            }
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, 'n')) => '\n',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'r')) => '\r',
+                    Some((_, '"')) => '"',
+                    Some((_, '\\')) => '\\',
+                    _ => return Err(error(i + 1, "invalid escape sequence")),
+                };
+                result.push(escaped);
+            }
+            c => result.push(c),
+        }
+    }
+    Err(error(value.len(), "unterminated string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            CatalogFormat::from_path("{baseDir}/{ns}.json"),
+            Some(CatalogFormat::Json)
+        );
+        assert_eq!(
+            CatalogFormat::from_path("{baseDir}/{locale}/{ns}.yml"),
+            Some(CatalogFormat::Yaml)
+        );
+        assert_eq!(
+            CatalogFormat::from_path("{baseDir}/{ns}.po"),
+            Some(CatalogFormat::Po)
+        );
+        assert_eq!(CatalogFormat::from_path("{baseDir}/{ns}"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            CatalogFormat::Json.parse("{\n  \"a\": }", "en"),
+            Err(ParseError {
+                line: 2,
+                column: 8,
+                message: "expected value".to_string(),
+            })
+        );
+        assert_eq!(
+            CatalogFormat::Json5.parse("{\n  a: 'b',\n  c: }", "en"),
+            Err(ParseError {
+                line: 3,
+                column: 6,
+                message: "expected array, boolean, null, number, object, or string".to_string(),
+            })
+        );
+        let error = CatalogFormat::Yaml
+            .parse("a: b\nc: [d\n", "en")
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            CatalogFormat::Po.parse("msgid \"a\"\nmsgstr \"b", "en"),
+            Err(ParseError {
+                line: 2,
+                column: 10,
+                message: "unterminated string".to_string(),
+            })
+        );
+        assert_eq!(
+            CatalogFormat::Po.parse("msgid \"a\"\n  msgstring \"b\"", "en"),
+            Err(ParseError {
+                line: 2,
+                column: 3,
+                message: "unknown keyword: msgstring".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_po() {
+        let content = r#"
+msgid ""
+msgstr ""
+"Plural-Forms: nplurals=3; plural=(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2);\n"
+
+#: src/app.tsx:1
+msgid "hello"
+msgstr "Привет, "
+"{{name}}\n"
+
+msgctxt "male"
+msgid "friend"
+msgstr "друг"
+
+msgid "apple"
+msgid_plural "apples"
+msgstr[0] "{{count}} яблоко"
+msgstr[1] "{{count}} яблока"
+msgstr[2] "{{count}} яблок"
+
+#, fuzzy
+msgid "draft"
+msgstr "черновик"
+
+msgid "untranslated"
+msgstr ""
+"#;
+        let Ok(Value::Object(map)) = CatalogFormat::Po.parse(content, "ru") else {
+            panic!("failed to parse");
+        };
+        assert_eq!(
+            map,
+            Map::from_iter([
+                ("hello".to_string(), Value::from("Привет, {{name}}\n")),
+                ("friend_male".to_string(), Value::from("друг")),
+                ("apple_one".to_string(), Value::from("{{count}} яблоко")),
+                ("apple_few".to_string(), Value::from("{{count}} яблока")),
+                ("apple_many".to_string(), Value::from("{{count}} яблок")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_po_plural_forms() {
+        let content = r#"
+msgid "apple"
+msgid_plural "apples"
+msgstr[0] "{{count}} pomme"
+msgstr[1] "{{count}} pommes"
+msgstr[2] "{{count}} de pommes"
+"#;
+        // nplurals defaults to the plural categories of the locale
+        let Ok(Value::Object(map)) = CatalogFormat::Po.parse(content, "fr") else {
+            panic!("failed to parse");
+        };
+        assert_eq!(
+            map.keys().collect::<Vec<_>>(),
+            ["apple_many", "apple_one", "apple_other"]
+        );
+        // older catalogs of fr only have 2 forms
+        let header = "msgid \"\"\nmsgstr \"Plural-Forms: nplurals=2; plural=(n > 1);\\n\"\n";
+        let Ok(Value::Object(map)) =
+            CatalogFormat::Po.parse(&format!("{}{}", header, content), "fr")
+        else {
+            panic!("failed to parse");
+        };
+        assert_eq!(map.keys().collect::<Vec<_>>(), ["apple_one", "apple_other"]);
+        assert_eq!(
+            CatalogFormat::Po.parse(content, "hr"),
+            Err(ParseError {
+                line: 2,
+                column: 1,
+                message: "unknown plural forms of locale: hr".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::ecma::{
+    ast::{
+        Decl, Expr, Id, Ident, ImportSpecifier, ModuleDecl, ModuleItem, ObjectPatProp, Pat, Stmt,
+        VarDeclarator,
+    },
+    visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+};
+
+/// Counts occurrences of identifiers including their declarations
+pub struct Usages {
+    counts: HashMap<Id, usize>,
+}
+
+impl Visit for Usages {
+    noop_visit_type!();
+
+    fn visit_ident(&mut self, n: &Ident) {
+        *self.counts.entry(n.to_id()).or_default() += 1;
+    }
+}
+
+/// Returns identifiers used besides their declarations
+pub fn used_ids<N: VisitWith<Usages>>(n: &N) -> HashSet<Id> {
+    let mut usages = Usages {
+        counts: HashMap::new(),
+    };
+    n.visit_with(&mut usages);
+    usages
+        .counts
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+/// Returns true for expressions without side effects e.g) this.props, props
+fn is_pure(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(_) | Expr::This(_) => true,
+        Expr::Member(member) => member.prop.is_ident() && is_pure(&member.obj),
+        _ => false,
+    }
+}
+
+/// Removes bindings unused in the pattern and returns true if nothing is left
+/// e.g) { t, i18n } -> { i18n }, [t, i18n] -> [, i18n]
+fn prune_pat(pat: &mut Pat, is_removable: &impl Fn(&Id) -> bool) -> bool {
+    match pat {
+        Pat::Ident(binding) => is_removable(&binding.id.to_id()),
+        Pat::Object(obj) => {
+            // removing a property changes the rest e.g) { t, ...rest }
+            if obj
+                .props
+                .iter()
+                .any(|prop| matches!(prop, ObjectPatProp::Rest(_)))
+            {
+                return obj.props.iter_mut().all(|prop| match prop {
+                    ObjectPatProp::KeyValue(kv) => prune_pat(&mut kv.value, is_removable),
+                    ObjectPatProp::Assign(assign) => is_removable(&assign.key.id.to_id()),
+                    ObjectPatProp::Rest(rest) => prune_pat(&mut rest.arg, is_removable),
+                });
+            }
+            obj.props.retain_mut(|prop| match prop {
+                ObjectPatProp::KeyValue(kv) => !prune_pat(&mut kv.value, is_removable),
+                ObjectPatProp::Assign(assign) => !is_removable(&assign.key.id.to_id()),
+                ObjectPatProp::Rest(_) => true,
+            });
+            obj.props.is_empty()
+        }
+        Pat::Array(array) => {
+            for elem in array.elems.iter_mut() {
+                if elem
+                    .as_mut()
+                    .is_some_and(|pat| !matches!(pat, Pat::Rest(_)) && prune_pat(pat, is_removable))
+                {
+                    *elem = None;
+                }
+            }
+            while matches!(array.elems.last(), Some(None)) {
+                array.elems.pop();
+            }
+            array.elems.is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// Removes declarations and imports of translations which became unused by inlining
+/// e.g) const { t, i18n } = useTranslation("foo") -> const { i18n } = useTranslation("foo")
+pub struct Pruner<'a> {
+    pub used: &'a HashSet<Id>,
+    /// bindings of translations removable wherever they are declared e.g) const { t } = this.props
+    pub ids: &'a HashSet<Id>,
+    /// returns true for initializers of translations e.g) useTranslation("foo"), require("react-i18next")
+    pub is_translation: &'a dyn Fn(&Expr) -> bool,
+    pub package_names: &'a [String],
+    pub changed: bool,
+}
+
+impl Pruner<'_> {
+    /// Returns true if the declarator is removed entirely
+    fn prune_declarator(&mut self, decl: &mut VarDeclarator) -> bool {
+        let from_translation = decl
+            .init
+            .as_deref()
+            .is_some_and(|init| (self.is_translation)(init));
+        let is_removable =
+            |id: &Id| !self.used.contains(id) && (from_translation || self.ids.contains(id));
+        let mut name = decl.name.clone();
+        if prune_pat(&mut name, &is_removable) {
+            // e.g) const { t } = this.props has no side effects
+            if from_translation || decl.init.as_deref().is_none_or(is_pure) {
+                self.changed = true;
+                return true;
+            }
+            return false;
+        }
+        if name != decl.name {
+            decl.name = name;
+            self.changed = true;
+        }
+        false
+    }
+
+    fn prune_stmt(&mut self, stmt: &mut Stmt) -> bool {
+        let Stmt::Decl(Decl::Var(var)) = stmt else {
+            return false;
+        };
+        var.decls.retain_mut(|decl| !self.prune_declarator(decl));
+        var.decls.is_empty()
+    }
+}
+
+impl VisitMut for Pruner<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
+        n.visit_mut_children_with(self);
+        n.retain_mut(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(decl))
+                if self
+                    .package_names
+                    .iter()
+                    .any(|name| *name == *decl.src.value) =>
+            {
+                // e.g) import "react-i18next" is kept for side effects
+                if decl.specifiers.is_empty() {
+                    return true;
+                }
+                let len = decl.specifiers.len();
+                decl.specifiers.retain(|specifier| {
+                    let local = match specifier {
+                        ImportSpecifier::Named(named) => &named.local,
+                        ImportSpecifier::Default(default) => &default.local,
+                        ImportSpecifier::Namespace(namespace) => &namespace.local,
+                    };
+                    self.used.contains(&local.to_id())
+                });
+                self.changed |= decl.specifiers.len() != len;
+                !decl.specifiers.is_empty()
+            }
+            ModuleItem::Stmt(stmt) => !self.prune_stmt(stmt),
+            _ => true,
+        });
+    }
+
+    fn visit_mut_stmts(&mut self, n: &mut Vec<Stmt>) {
+        n.visit_mut_children_with(self);
+        n.retain_mut(|stmt| !self.prune_stmt(stmt));
+    }
+}
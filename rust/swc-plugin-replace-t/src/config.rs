@@ -1,7 +1,277 @@
-use serde::Deserialize;
+use std::collections::HashMap;
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+use crate::catalog::CatalogFormat;
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     pub base_dir: String,
+    /// locale of catalogs to inline e.g) "en"
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// locales to look up in order when a key is missing in the locale e.g) ["en"]
+    #[serde(default)]
+    pub fallback_locales: Vec<String>,
+    /// file which keys served from fallback locales are written to as JSON lines
+    /// e.g) "{baseDir}/fallbacks.{locale}.jsonl" -> {"from":"en","key":"farewell","locale":"de","namespace":"common"}
+    #[serde(default)]
+    pub fallback_report: Option<String>,
+    /// path of catalogs e.g) "{baseDir}/{locale}/{ns}.json"
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// format of catalogs inferred from extension of path by default e.g) "yaml", "po"
+    #[serde(default)]
+    pub format: Option<CatalogFormat>,
+    /// separator of namespace and key e.g) "common:foo", or false to disable
+    #[serde(
+        default = "default_ns_separator",
+        deserialize_with = "deserialize_separator"
+    )]
+    pub ns_separator: Option<String>,
+    /// separator of nested keys e.g) "foo.bar", or false for flat keys
+    #[serde(
+        default = "default_key_separator",
+        deserialize_with = "deserialize_separator"
+    )]
+    pub key_separator: Option<String>,
+    /// names of translation functions returned by hooks e.g) const { t } = useTranslation("ns")
+    #[serde(default = "default_method_names")]
+    pub method_names: Vec<String>,
+    /// names of hooks returning translation functions e.g) const { t } = useTranslation("ns")
+    #[serde(default = "default_hook_names")]
+    pub hook_names: Vec<String>,
+    /// names of translation components e.g) <Trans i18nKey="foo" />
+    #[serde(default = "default_component_names")]
+    pub component_names: Vec<String>,
+    /// async factories of translation functions e.g) const { t } = await getTranslation(lng, "ns")
+    #[serde(default = "default_async_hooks")]
+    pub async_hooks: Vec<AsyncHook>,
+    /// names of HOCs passing translation functions as props e.g) withTranslation("ns")(Component)
+    #[serde(default = "default_hoc_names")]
+    pub hoc_names: Vec<String>,
+    /// names of render-prop components e.g) <Translation ns="ns">{(t) => t("foo")}</Translation>
+    #[serde(default = "default_render_prop_names")]
+    pub render_prop_names: Vec<String>,
+    /// name of the attribute mapping tags to elements e.g) <Trans components={{ bold: <b /> }} />
+    #[serde(default = "default_components_attr_name")]
+    pub components_attr_name: String,
+    /// packages whose imports are removed and aliased e.g) import { useTranslation as useT } from "react-i18next"
+    #[serde(default = "default_package_names")]
+    pub package_names: Vec<String>,
+    /// names of imported i18next instances e.g) i18next.t("common:foo"), i18n.getFixedT(null, "common")
+    #[serde(default = "default_instance_names")]
+    pub instance_names: Vec<String>,
+    /// namespace of keys without namespaces out of useTranslation as defaultNS of i18next
+    /// e.g) i18next.t("foo") -> "translation:foo"
+    #[serde(default = "default_default_ns", alias = "defaultNS")]
+    pub default_ns: String,
+    /// escapes interpolated values for HTML as i18next does, except {{- raw}}
+    /// off by default as React escapes texts e.g) <p>{t("foo", { name })}</p>
+    #[serde(default)]
+    pub escape_value: bool,
+    /// how to report variables of options missing in or unused by messages
+    #[serde(default)]
+    pub variable_check: VariableCheck,
+    /// how to handle a key missing in catalogs
+    #[serde(default)]
+    pub missing_key: MissingKey,
+    /// missingKey for each namespace e.g) { "admin": "warn" }
+    #[serde(default)]
+    pub missing_key_by_namespace: HashMap<String, MissingKey>,
+}
+
+/// An async factory of translation functions for server components
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AsyncHook {
+    pub name: String,
+    /// position of the lng argument, or none e.g) 0 for getTranslation(lng, "ns")
+    #[serde(default)]
+    pub lng_index: Option<usize>,
+}
+
+/// A policy for variables not matching placeholders e.g) t("foo", { min }) for "{{min}} - {{max}}"
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum VariableCheck {
+    /// fails the build
+    Error,
+    /// warns and inlines the translation
+    #[default]
+    Warn,
+    /// inlines the translation silently
+    Off,
+}
+
+/// A policy for a key missing in catalogs
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MissingKey {
+    /// fails the build
+    #[default]
+    Error,
+    /// warns and leaves t() for runtime
+    Warn,
+    /// leaves t() for runtime
+    Keep,
+    /// replaces t() with the key
+    Key,
+    /// replaces <Trans /> with its children, or the key without children
+    /// defaultValue of t() is used in any mode
+    Default,
+}
+
+impl MissingKey {
+    /// Returns true if the translation is left for runtime
+    pub fn keeps_runtime(self) -> bool {
+        matches!(self, Self::Warn | Self::Keep)
+    }
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_path() -> String {
+    "{baseDir}/{ns}.json".to_string()
+}
+
+fn default_method_names() -> Vec<String> {
+    vec!["t".to_string()]
+}
+
+fn default_hook_names() -> Vec<String> {
+    vec!["useTranslation".to_string()]
+}
+
+fn default_component_names() -> Vec<String> {
+    vec!["Trans".to_string()]
+}
+
+fn default_async_hooks() -> Vec<AsyncHook> {
+    vec![
+        AsyncHook {
+            name: "getTranslation".to_string(),
+            lng_index: Some(0),
+        },
+        AsyncHook {
+            name: "getTranslations".to_string(),
+            lng_index: None,
+        },
+    ]
+}
+
+fn default_hoc_names() -> Vec<String> {
+    vec!["withTranslation".to_string()]
+}
+
+fn default_render_prop_names() -> Vec<String> {
+    vec!["Translation".to_string()]
+}
+
+fn default_components_attr_name() -> String {
+    "components".to_string()
+}
+
+fn default_package_names() -> Vec<String> {
+    vec!["react-i18next".to_string(), "next-i18next".to_string()]
+}
+
+fn default_instance_names() -> Vec<String> {
+    vec!["i18next".to_string(), "i18n".to_string()]
+}
+
+fn default_default_ns() -> String {
+    "translation".to_string()
+}
+
+fn default_ns_separator() -> Option<String> {
+    Some(":".to_string())
+}
+
+fn default_key_separator() -> Option<String> {
+    Some(".".to_string())
+}
+
+/// Deserializes a separator as i18next does e.g) ":" -> Some(":"), false -> None
+fn deserialize_separator<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Separator {
+        Str(String),
+        Bool(bool),
+    }
+
+    match Separator::deserialize(deserializer)? {
+        Separator::Str(separator) => Ok(Some(separator)),
+        Separator::Bool(false) => Ok(None),
+        Separator::Bool(true) => Err(D::Error::custom("separator must be a string or false")),
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_dir: "".to_string(),
+            locale: default_locale(),
+            fallback_locales: vec![],
+            fallback_report: None,
+            path: default_path(),
+            format: None,
+            ns_separator: default_ns_separator(),
+            key_separator: default_key_separator(),
+            method_names: default_method_names(),
+            hook_names: default_hook_names(),
+            component_names: default_component_names(),
+            async_hooks: default_async_hooks(),
+            hoc_names: default_hoc_names(),
+            render_prop_names: default_render_prop_names(),
+            components_attr_name: default_components_attr_name(),
+            package_names: default_package_names(),
+            instance_names: default_instance_names(),
+            default_ns: default_default_ns(),
+            escape_value: false,
+            variable_check: VariableCheck::default(),
+            missing_key: MissingKey::default(),
+            missing_key_by_namespace: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_separators() {
+        let config: Config = serde_json::from_str(r#"{ "baseDir": "locales" }"#).unwrap();
+        assert_eq!(config.ns_separator.as_deref(), Some(":"));
+        assert_eq!(config.key_separator.as_deref(), Some("."));
+
+        let config: Config = serde_json::from_str(
+            r#"{ "baseDir": "locales", "nsSeparator": "::", "keySeparator": false }"#,
+        )
+        .unwrap();
+        assert_eq!(config.ns_separator.as_deref(), Some("::"));
+        assert_eq!(config.key_separator, None);
+
+        assert!(
+            serde_json::from_str::<Config>(r#"{ "baseDir": "", "nsSeparator": true }"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_ns() {
+        let config: Config = serde_json::from_str(r#"{ "baseDir": "locales" }"#).unwrap();
+        assert_eq!(config.default_ns, "translation");
+
+        let config: Config =
+            serde_json::from_str(r#"{ "baseDir": "locales", "defaultNS": "common" }"#).unwrap();
+        assert_eq!(config.default_ns, "common");
+    }
 }
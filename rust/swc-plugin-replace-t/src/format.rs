@@ -0,0 +1,202 @@
+use serde_json::{Map, Number, Value};
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::ast::{
+        Bool, Expr, Ident, KeyValueProp, Lit, ObjectLit, Prop, PropName, PropOrSpread, Str,
+    },
+    quote_expr,
+};
+
+/// A formatter of interpolation e.g) {{price, currency(USD)}}
+/// https://www.i18next.com/translation-function/formatting
+#[derive(Debug, PartialEq)]
+pub enum Format {
+    Number(Map<String, Value>),
+    Currency(Map<String, Value>),
+    DateTime(Map<String, Value>),
+    RelativeTime(String, Map<String, Value>),
+    List(Map<String, Value>),
+}
+
+impl Format {
+    /// Parses a formatter e.g) "number(minimumFractionDigits: 2)", "currency(USD)"
+    pub fn parse(format: &str) -> Result<Self, String> {
+        let format = format.trim();
+        let (name, args) = match format.split_once('(') {
+            Some((name, args)) => (name.trim(), args.trim_end().trim_end_matches(')').trim()),
+            None => (format, ""),
+        };
+        let mut options = Map::new();
+        let mut arg = None;
+        if args.contains(':') {
+            // e.g) datetime(weekday: long; month: long)
+            for option in args.split(';').filter(|option| !option.trim().is_empty()) {
+                let Some((key, value)) = option.split_once(':') else {
+                    return Err(format!("invalid option of formatter: {}", option.trim()));
+                };
+                options.insert(key.trim().to_string(), parse_option(value.trim()));
+            }
+        } else if !args.is_empty() {
+            // e.g) currency(USD), relativetime(quarter)
+            arg = Some(args.to_string());
+        }
+
+        match name.to_lowercase().as_str() {
+            "number" => Ok(Self::Number(options)),
+            "currency" => {
+                options.insert("style".to_string(), Value::from("currency"));
+                if let Some(currency) = arg {
+                    options.insert("currency".to_string(), Value::from(currency));
+                }
+                Ok(Self::Currency(options))
+            }
+            "datetime" => Ok(Self::DateTime(options)),
+            "relativetime" => {
+                let unit = arg
+                    .or_else(|| {
+                        options
+                            .remove("range")
+                            .and_then(|range| range.as_str().map(|range| range.to_string()))
+                    })
+                    // i18next falls back to "day" when neither the unit nor range is given
+                    .unwrap_or_else(|| "day".to_string());
+                Ok(Self::RelativeTime(unit, options))
+            }
+            "list" => Ok(Self::List(options)),
+            _ => Err(format!("unknown formatter: {}", name)),
+        }
+    }
+
+    /// Returns true if the output never contains characters escaped for HTML e.g) $1,234.50
+    pub fn is_html_safe(&self) -> bool {
+        matches!(self, Self::Number(_) | Self::Currency(_))
+    }
+
+    /// Makes an expression formatting the value with Intl
+    /// e.g) new Intl.NumberFormat("en", { style: "currency", currency: "USD" }).format(v.price)
+    pub fn to_expr(&self, value: Expr, locale: &str) -> Box<Expr> {
+        let locale = Expr::Lit(Lit::Str(locale.into()));
+        match self {
+            Self::Number(options) | Self::Currency(options) => quote_expr!(
+                "new Intl.NumberFormat($locale, $options).format($value)",
+                locale: Expr = locale,
+                options: Expr = object_from_map(options),
+                value: Expr = value
+            ),
+            Self::DateTime(options) => quote_expr!(
+                "new Intl.DateTimeFormat($locale, $options).format($value)",
+                locale: Expr = locale,
+                options: Expr = object_from_map(options),
+                value: Expr = value
+            ),
+            Self::RelativeTime(unit, options) => quote_expr!(
+                "new Intl.RelativeTimeFormat($locale, $options).format($value, $unit)",
+                locale: Expr = locale,
+                options: Expr = object_from_map(options),
+                value: Expr = value,
+                unit: Expr = Expr::Lit(Lit::Str(unit.as_str().into()))
+            ),
+            Self::List(options) => quote_expr!(
+                "new Intl.ListFormat($locale, $options).format($value)",
+                locale: Expr = locale,
+                options: Expr = object_from_map(options),
+                value: Expr = value
+            ),
+        }
+    }
+}
+
+/// Parses a value of option as i18next does e.g) "2" -> 2, "0.5" -> 0.5, "false" -> false
+fn parse_option(value: &str) -> Value {
+    match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        value => {
+            let number = match value.parse::<i64>() {
+                Ok(number) => Some(Number::from(number)),
+                Err(_) => value.parse::<f64>().ok().and_then(Number::from_f64),
+            };
+            match number {
+                Some(number) => Value::Number(number),
+                None => Value::from(value.trim_matches(|c| c == '"' || c == '\'')),
+            }
+        }
+    }
+}
+
+fn object_from_map(map: &Map<String, Value>) -> Expr {
+    let props = map
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Bool(value) => Expr::Lit(Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: *value,
+                })),
+                Value::Number(number) => Expr::from(number.as_f64().unwrap_or_default()),
+                value => Expr::Lit(Lit::Str(Str::from(
+                    value.as_str().unwrap_or_default().to_string(),
+                ))),
+            };
+            PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(Ident::new(key.as_str().into(), DUMMY_SP)),
+                value: Box::new(value),
+            })))
+        })
+        .collect();
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Format::parse("number"), Ok(Format::Number(Map::new())));
+        assert_eq!(
+            Format::parse("currency(USD)"),
+            Ok(Format::Currency(Map::from_iter([
+                ("style".to_string(), Value::from("currency")),
+                ("currency".to_string(), Value::from("USD")),
+            ])))
+        );
+        assert_eq!(
+            Format::parse("datetime(weekday: long; hour12: false; day: 2)"),
+            Ok(Format::DateTime(Map::from_iter([
+                ("weekday".to_string(), Value::from("long")),
+                ("hour12".to_string(), Value::from(false)),
+                ("day".to_string(), Value::from(2)),
+            ])))
+        );
+        assert_eq!(
+            Format::parse("number(minimumFractionDigits: 2; roundingIncrement: 0.5)"),
+            Ok(Format::Number(Map::from_iter([
+                ("minimumFractionDigits".to_string(), Value::from(2)),
+                ("roundingIncrement".to_string(), Value::from(0.5)),
+            ])))
+        );
+        assert_eq!(
+            Format::parse("number(notation: NaN)"),
+            Ok(Format::Number(Map::from_iter([(
+                "notation".to_string(),
+                Value::from("NaN")
+            )])))
+        );
+        assert_eq!(
+            Format::parse("relativetime(quarter)"),
+            Ok(Format::RelativeTime("quarter".to_string(), Map::new()))
+        );
+        assert_eq!(
+            Format::parse("relativeTime"),
+            Ok(Format::RelativeTime("day".to_string(), Map::new()))
+        );
+        assert_eq!(
+            Format::parse("uppercase"),
+            Err("unknown formatter: uppercase".to_string())
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{BTreeSet, HashMap};
+
+use swc_core::{
+    common::DUMMY_SP,
+    ecma::{
+        ast::{
+            CallExpr, Expr, ExprOrSpread, Id, Lit, MemberExpr, MemberProp, ObjectLit, Prop,
+            PropName, Stmt, Str, Tpl,
+        },
+        visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+    },
+};
+
+use crate::utils::{escape_html, props_from_object};
+
+/// Replaces placeholders of a template e.g) v.name with the values of options
+struct ValueInliner<'a> {
+    values: &'a HashMap<String, Box<Expr>>,
+    /// helper escaping values e.g) escape(v.name)
+    escape: &'a Id,
+    missing: bool,
+}
+
+impl ValueInliner<'_> {
+    /// Escapes a literal at compile time e.g) escape("<b>") -> "&lt;b&gt;"
+    fn escape_lit(&self, call: &CallExpr) -> Option<Expr> {
+        let callee = call.callee.as_expr()?.as_ident()?;
+        if callee.to_id() != *self.escape {
+            return None;
+        }
+        let [arg] = call.args.as_slice() else {
+            return None;
+        };
+        let value = string_from_lit(arg.expr.as_lit()?)?;
+        Some(Expr::Lit(Lit::Str(Str::from(escape_html(&value)))))
+    }
+}
+
+impl VisitMut for ValueInliner<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        if let Expr::Call(call) = n {
+            call.visit_mut_children_with(self);
+            if let Some(lit) = self.escape_lit(call) {
+                *n = lit;
+            }
+            return;
+        }
+        let Expr::Member(MemberExpr { obj, prop, .. }) = n else {
+            return n.visit_mut_children_with(self);
+        };
+        if obj.as_ident().is_none_or(|obj| &obj.sym != "v") {
+            return n.visit_mut_children_with(self);
+        }
+        let name = match prop {
+            MemberProp::Ident(ident) => ident.sym.to_string(),
+            MemberProp::Computed(computed) => match &*computed.expr {
+                Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+                _ => return self.missing = true,
+            },
+            MemberProp::PrivateName(_) => return self.missing = true,
+        };
+        match self.values.get(&name) {
+            Some(value) => *n = (**value).clone(),
+            None => self.missing = true,
+        }
+    }
+}
+
+/// Returns true if every property is a plain key with an identifier or a literal
+/// which can be read in any order or not at all
+/// e.g) { name, count: 1 } but not { name: user.name }, { name: getName() }, { ...values }
+pub fn is_inlinable(obj: &ObjectLit) -> bool {
+    obj.props.iter().all(|prop| {
+        prop.as_prop().is_some_and(|prop| match &**prop {
+            Prop::Shorthand(_) => true,
+            Prop::KeyValue(kv) => {
+                (kv.key.is_ident() || kv.key.is_str()) && (kv.value.is_lit() || kv.value.is_ident())
+            }
+            _ => false,
+        })
+    })
+}
+
+/// Converts a literal into the string of its interpolation e.g) 1 -> "1", true -> "true"
+fn string_from_lit(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Str(str) => Some(str.value.to_string()),
+        Lit::Bool(bool) => Some(bool.value.to_string()),
+        Lit::Null(_) => Some("null".to_string()),
+        // JavaScript switches to exponents out of this range
+        Lit::Num(num) if num.value == 0.0 => Some("0".to_string()),
+        Lit::Num(num) if num.value.is_finite() && (1e-6..1e21).contains(&num.value.abs()) => {
+            Some(num.value.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Folds a template whose expressions are all literals into a string
+/// e.g) `value: ${400}` -> "value: 400"
+fn fold_template(tpl: &Tpl) -> Option<String> {
+    let mut value = String::new();
+    for (i, quasi) in tpl.quasis.iter().enumerate() {
+        value.push_str(quasi.cooked.as_deref()?);
+        if let Some(expr) = tpl.exprs.get(i) {
+            value.push_str(&string_from_lit(expr.as_lit()?)?);
+        }
+    }
+    Some(value)
+}
+
+/// Inlines values of an object literal into a template without the function call
+/// e.g) `value: ${v.max}` with { max } -> `value: ${max}`, with { max: 400 } -> "value: 400"
+pub fn inline_values(template: &Expr, options: &ExprOrSpread, escape: &Id) -> Option<Box<Expr>> {
+    if options.spread.is_some() {
+        return None;
+    }
+    let Expr::Object(obj) = &*options.expr else {
+        return None;
+    };
+    // reordering or dropping values e.g) getters, calls could change side effects
+    if !is_inlinable(obj) {
+        return None;
+    }
+    let values = props_from_object(obj);
+    let mut inliner = ValueInliner {
+        values: &values,
+        escape,
+        missing: false,
+    };
+    let mut template = Box::new(template.clone());
+    template.visit_mut_with(&mut inliner);
+    // e.g) t("foo", { min }) for "{{max}}" keeps v.max undefined at runtime
+    if inliner.missing {
+        return None;
+    }
+    match &*template {
+        Expr::Tpl(tpl) => match fold_template(tpl) {
+            Some(value) => Some(Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.into(),
+                raw: None,
+            })))),
+            None => Some(template),
+        },
+        _ => Some(template),
+    }
+}
+
+/// Collects names of values read by a template e.g) v.max, v.user.name
+struct ValueNames {
+    names: BTreeSet<String>,
+}
+
+impl Visit for ValueNames {
+    noop_visit_type!();
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if n.obj.as_ident().is_some_and(|obj| &obj.sym == "v") {
+            match &n.prop {
+                MemberProp::Ident(ident) => {
+                    self.names.insert(ident.sym.to_string());
+                }
+                MemberProp::Computed(computed) => {
+                    if let Expr::Lit(Lit::Str(str)) = &*computed.expr {
+                        self.names.insert(str.value.to_string());
+                    }
+                }
+                MemberProp::PrivateName(_) => {}
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Returns names of values read by a statement of a translation
+/// e.g) return `${v.user.name} ${v.count}`; -> ["count", "user"]
+pub fn value_names(stmt: &Stmt) -> BTreeSet<String> {
+    let mut collector = ValueNames {
+        names: BTreeSet::new(),
+    };
+    stmt.visit_with(&mut collector);
+    collector.names
+}
+
+/// Returns keys of an object literal, or None if some keys are unknown e.g) { ...values }
+pub fn static_keys(obj: &ObjectLit) -> Option<BTreeSet<String>> {
+    obj.props
+        .iter()
+        .map(|prop| match &**prop.as_prop()? {
+            Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+            Prop::KeyValue(kv) => match &kv.key {
+                PropName::Ident(ident) => Some(ident.sym.to_string()),
+                PropName::Str(str) => Some(str.value.to_string()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
@@ -1,146 +1,761 @@
 mod cache;
+mod catalog;
+mod cleanup;
 mod config;
+mod format;
+mod inline;
+mod nesting;
+mod plural;
 mod utils;
+mod wrapper;
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+};
 
-use cache::Cache;
-use config::Config;
+use cache::{write_fallbacks, Cache, CacheError};
+use cleanup::{used_ids, Pruner, Usages};
+use config::{Config, MissingKey, VariableCheck};
+use format::Format;
+use inline::{inline_values, is_inlinable, static_keys, value_names};
+use nesting::{find_nestings, interpolate_options, option_placeholder, parse_options, Nesting};
+use plural::plural_category;
 use swc_core::{
-    common::{util::take::Take, DUMMY_SP},
+    common::{Span, Spanned, DUMMY_SP},
     ecma::{
         ast::{
-            ArrayLit, ArrowExpr, BindingIdent, BlockStmtOrExpr, CallExpr, Callee, Decl, Expr,
-            ExprOrSpread, Ident, JSXAttrName, JSXAttrOrSpread, JSXAttrValue, JSXClosingElement,
-            JSXClosingFragment, JSXElement, JSXElementChild, JSXElementName, JSXExpr, JSXFragment,
-            JSXOpeningElement, JSXOpeningFragment, KeyValueProp, Lit, ModuleDecl, ModuleItem,
-            ObjectLit, Pat, Program, Prop, PropName, PropOrSpread, Stmt, Str, VarDecl,
+            ArrayLit, ArrowExpr, AwaitExpr, BindingIdent, BlockStmt, BlockStmtOrExpr, CallExpr,
+            Callee, ClassDecl, ComputedPropName, Decl, Expr, ExprOrSpread, FnDecl, Function, Id,
+            Ident, ImportDecl, ImportSpecifier, JSXAttrName, JSXAttrOrSpread, JSXAttrValue,
+            JSXClosingElement, JSXClosingFragment, JSXElement, JSXElementChild, JSXElementName,
+            JSXExpr, JSXExprContainer, JSXFragment, JSXOpeningElement, JSXOpeningFragment, JSXText,
+            KeyValuePatProp, KeyValueProp, Lit, MemberExpr, MemberProp, Module, ModuleDecl,
+            ModuleExportName, ModuleItem, ObjectLit, ObjectPatProp, ParenExpr, Pat, Program, Prop,
+            PropName, PropOrSpread, ReturnStmt, Script, Stmt, Str, SwitchCase, SwitchStmt, Tpl,
+            TplElement, VarDecl, VarDeclarator,
         },
-        atoms::JsWord,
-        visit::{as_folder, noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith},
+        utils::{collect_decls, contains_ident_ref, private_ident},
+        visit::{as_folder, noop_visit_mut_type, FoldWith, VisitMut, VisitMutWith, VisitWith},
     },
     plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
+    quote, quote_expr,
+};
+use utils::{
+    context_from_options, count_from_options, default_value_from_options, emit_error, emit_warning,
+    escape_template, json_path_from_key, jsx_attr_expr, jsx_attr_str, number_from_expr,
+    parse_message, props_from_object, remove_prop, tokenize, translation_bindings, MessageNode,
+    Segment,
 };
-use utils::{emit_error, json_path_from_key, parse_code};
+use wrapper::{is_props, render_prop, render_prop_body, wrapped_components, References, Unwrapper};
+
+/// options of t() which are not interpolation variables
+/// https://www.i18next.com/translation-function/essentials#overview-options
+const OPTION_NAMES: [&str; 13] = [
+    "count",
+    "context",
+    "ns",
+    "lng",
+    "lngs",
+    "fallbackLng",
+    "keyPrefix",
+    "replace",
+    "returnObjects",
+    "returnDetails",
+    "joinArrays",
+    "postProcess",
+    "interpolation",
+];
 
-const TRANSLATION_METHOD_NAME: &str = "t";
-const TRANSLATION_HOOKS_NAME: &str = "useTranslation";
-const TRANSLATION_COMPONENT_NAME: &str = "Trans";
-const TRANSLATION_COMPONENT_ATTR_NAME: &str = "components";
-const TRANSLATION_PACKAGE_NAME: &str = "react-i18next";
+/// tags kept without mappings as react-i18next does e.g) "hello<br />world"
+const KEEP_BASIC_HTML_NODES: [&str; 4] = ["br", "strong", "i", "p"];
+
+/// Namespaces and options of useTranslation
+/// e.g) useTranslation(["admin", "common"], { keyPrefix: "form", lng: "de" })
+#[derive(Clone, Default)]
+struct Scope {
+    namespaces: Vec<String>,
+    key_prefix: Option<String>,
+    /// a locale pinned by lng instead of the locale of config
+    locale: Option<String>,
+}
 
 pub struct TransformVisitor {
-    scopes: Vec<String>,
+    /// useTranslation stacked by functions
+    scopes: Vec<Scope>,
+    /// bindings declared in the program e.g) const t = ...
+    declared: HashSet<Id>,
+    /// locale of config
+    default_locale: String,
+    /// locale of the translation being inlined
+    locale: String,
+    ns_separator: Option<String>,
+    key_separator: Option<String>,
+    /// bindings of translation functions with scopes of their hooks, None for imports
+    /// e.g) const { t: translate } = useTranslation("common")
+    translation_ids: HashMap<Id, Option<Scope>>,
+    /// names of translation functions, hooks and components including aliases of imports
+    method_names: HashSet<String>,
+    hook_names: HashSet<String>,
+    component_names: HashSet<String>,
+    components_attr_name: String,
+    package_names: Vec<String>,
+    /// names of i18next instances e.g) i18next.t("foo"), i18n.getFixedT(null, "foo")
+    instance_names: HashSet<String>,
+    /// bindings of i18next instances imported or required by the names
+    instance_ids: HashSet<Id>,
+    default_ns: String,
+    /// async hooks with positions of lng e.g) { "getTranslation": Some(0) }
+    async_hooks: HashMap<String, Option<usize>>,
+    hoc_names: HashSet<String>,
+    render_prop_names: HashSet<String>,
+    /// components wrapped by withTranslation with its scopes
+    wrapped: HashMap<Id, Scope>,
+    /// scope of t in props of the wrapped component being visited with its props parameter
+    props_scope: Option<(Scope, Option<Id>)>,
+    /// wrapped components whose translations are all inlined
+    unwrapped: HashSet<Id>,
     cache: Cache,
-    component_map: HashMap<String, JSXElement>,
+    /// mappings of tags to elements stacked by <Trans /> e.g) [{ "bold": <strong /> }]
+    component_maps: Vec<HashMap<String, JSXElement>>,
+    missing_key: MissingKey,
+    missing_key_by_namespace: HashMap<String, MissingKey>,
+    /// spans of <Trans /> left for runtime by missingKey
+    runtime_elements: HashSet<Span>,
+    /// file recording keys served from fallback locales
+    fallback_report: Option<PathBuf>,
+    escape_value: bool,
+    variable_check: VariableCheck,
+    /// helper escaping interpolated values e.g) escape(v.name)
+    escape: Ident,
 }
 
 impl TransformVisitor {
-    pub fn new(base_dir: String) -> Self {
+    pub fn new(config: Config) -> Self {
         Self {
             scopes: vec![],
-            component_map: HashMap::new(),
-            cache: Cache::new(base_dir),
+            declared: HashSet::new(),
+            default_locale: config.locale.clone(),
+            locale: config.locale.clone(),
+            ns_separator: config.ns_separator.clone(),
+            key_separator: config.key_separator.clone(),
+            translation_ids: HashMap::new(),
+            method_names: HashSet::from_iter(config.method_names.clone()),
+            hook_names: HashSet::from_iter(config.hook_names.clone()),
+            component_names: HashSet::from_iter(config.component_names.clone()),
+            components_attr_name: config.components_attr_name.clone(),
+            package_names: config.package_names.clone(),
+            instance_names: HashSet::from_iter(config.instance_names.clone()),
+            instance_ids: HashSet::new(),
+            default_ns: config.default_ns.clone(),
+            async_hooks: config
+                .async_hooks
+                .iter()
+                .map(|hook| (hook.name.clone(), hook.lng_index))
+                .collect(),
+            hoc_names: HashSet::from_iter(config.hoc_names.clone()),
+            render_prop_names: HashSet::from_iter(config.render_prop_names.clone()),
+            wrapped: HashMap::new(),
+            props_scope: None,
+            unwrapped: HashSet::new(),
+            component_maps: vec![],
+            cache: Cache::new(&config),
+            missing_key: config.missing_key,
+            missing_key_by_namespace: config.missing_key_by_namespace,
+            runtime_elements: HashSet::new(),
+            // e.g) {baseDir}/fallbacks.{locale}.jsonl -> /cwd/locales/fallbacks.de.jsonl
+            fallback_report: config.fallback_report.as_ref().map(|report| {
+                PathBuf::from("/cwd").join(
+                    report
+                        .replace("{baseDir}", &config.base_dir)
+                        .replace("{locale}", &config.locale),
+                )
+            }),
+            escape_value: config.escape_value,
+            variable_check: config.variable_check,
+            escape: private_ident!("escape"),
         }
     }
-}
 
-impl VisitMut for TransformVisitor {
-    noop_visit_mut_type!();
+    /// Returns the innermost useTranslation
+    fn current_scope(&self) -> Scope {
+        self.scopes.last().cloned().unwrap_or_default()
+    }
 
-    fn visit_mut_module_items(&mut self, n: &mut Vec<ModuleItem>) {
-        n.visit_mut_children_with(self);
-        // remove invalid import statement
-        n.retain(|s| !matches!(s, ModuleItem::Stmt(Stmt::Empty(..))));
+    /// Switches the locale of catalogs to lng of useTranslation, or back to the locale of config
+    fn set_locale(&mut self, locale: Option<&str>) {
+        self.locale = locale.unwrap_or(&self.default_locale).to_string();
+        self.cache.set_locale(&self.locale);
+    }
+
+    /// Returns missingKey for the namespace
+    fn missing_key_mode(&self, filename: &str) -> MissingKey {
+        self.missing_key_by_namespace
+            .get(filename)
+            .copied()
+            .unwrap_or(self.missing_key)
+    }
+
+    /// Handles a missing key by missingKey and returns a node to replace the translation with
+    /// None leaves the translation as it is
+    fn missing_key(
+        &mut self,
+        filename: &str,
+        path: &str,
+        key: &str,
+        default: Option<Box<Expr>>,
+        span: Span,
+    ) -> Option<Box<Expr>> {
+        match self.missing_key_mode(filename) {
+            MissingKey::Error => {
+                emit_error(&format!("key: {} not found", key), span);
+                None
+            }
+            MissingKey::Warn => {
+                emit_warning(&format!("key: {} not found", key), span);
+                None
+            }
+            MissingKey::Keep => None,
+            MissingKey::Key => Some(Box::new(Expr::Lit(Lit::Str(path.into())))),
+            MissingKey::Default => {
+                Some(default.unwrap_or_else(|| Box::new(Expr::Lit(Lit::Str(path.into())))))
+            }
+        }
+    }
+
+    /// Returns a call of an async hook with the position of lng e.g) await getTranslation(lng, "xxx")
+    fn async_hook_call<'a>(&self, expr: &'a Expr) -> Option<(&'a CallExpr, Option<usize>)> {
+        let Expr::Await(AwaitExpr { arg, .. }) = expr else {
+            return None;
+        };
+        let Expr::Call(call_expr) = &**arg else {
+            return None;
+        };
+        let Callee::Expr(callee_expr) = &call_expr.callee else {
+            return None;
+        };
+        let Expr::Ident(ident) = &**callee_expr else {
+            return None;
+        };
+        let lng_index = self.async_hooks.get(&*ident.sym)?;
+        Some((call_expr, *lng_index))
+    }
+
+    /// Returns true for initializers of translations removed once unused
+    /// e.g) useTranslation("xxx"), await getTranslation(lng, "xxx"), require("react-i18next")
+    fn is_translation_init(&self, expr: &Expr) -> bool {
+        if self.async_hook_call(expr).is_some() {
+            return true;
+        }
+        let Expr::Call(call_expr) = expr else {
+            return false;
+        };
+        let Callee::Expr(callee_expr) = &call_expr.callee else {
+            return false;
+        };
+        match &**callee_expr {
+            Expr::Ident(ident) => {
+                self.hook_names.contains(&*ident.sym)
+                    || (&ident.sym == "require" && self.require_src(call_expr).is_some())
+            }
+            // e.g) i18next.getFixedT(null, "xxx")
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => &prop.sym == "getFixedT" && self.is_instance(obj),
+            _ => false,
+        }
     }
 
-    fn visit_mut_module_item(&mut self, n: &mut ModuleItem) {
+    /// Returns the package of require("react-i18next")
+    fn require_src<'a>(&self, call_expr: &'a CallExpr) -> Option<&'a str> {
+        match call_expr.args.first().map(|arg| &*arg.expr) {
+            Some(Expr::Lit(Lit::Str(src)))
+                if self.package_names.iter().any(|name| *name == *src.value) =>
+            {
+                Some(&src.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes declarations and imports of translations which became unused
+    fn cleanup<N>(&self, n: &mut N)
+    where
+        N: VisitWith<Usages> + for<'a> VisitMutWith<Pruner<'a>>,
+    {
+        let ids = self.translation_ids.keys().cloned().collect();
+        // removing a declaration may leave its hook unused e.g) useTranslation of an import
+        loop {
+            let used = used_ids(&*n);
+            let mut pruner = Pruner {
+                used: &used,
+                ids: &ids,
+                is_translation: &|expr| self.is_translation_init(expr),
+                package_names: &self.package_names,
+                changed: false,
+            };
+            n.visit_mut_with(&mut pruner);
+            if !pruner.changed {
+                break;
+            }
+        }
+    }
+
+    /// Returns true for i18next instances e.g) i18next, i18n
+    fn is_instance(&self, expr: &Expr) -> bool {
+        matches!(expr, Expr::Ident(ident) if self.instance_ids.contains(&ident.to_id()))
+    }
+
+    /// Returns a scope of arguments of useTranslation(ns, options) or withTranslation(ns, options)
+    fn hook_scope(&mut self, args: &[ExprOrSpread], locale: Option<String>) -> Scope {
+        let mut scope = Scope {
+            locale,
+            ..Default::default()
+        };
+        if let Some(Expr::Object(options)) = args.get(1).map(|arg| &*arg.expr) {
+            // e.g) useTranslation("foo", { keyPrefix: "bar", lng: "de" })
+            for (name, value) in props_from_object(options) {
+                let target = match name.as_str() {
+                    "keyPrefix" => &mut scope.key_prefix,
+                    "lng" => &mut scope.locale,
+                    _ => continue,
+                };
+                match *value {
+                    Expr::Lit(Lit::Str(value)) => *target = Some(value.value.to_string()),
+                    _ => emit_error(&format!("{} must be a string literal", name), options.span),
+                }
+            }
+        }
+        if let Some(arg) = args.first() {
+            scope.namespaces = self.load_namespaces(&arg.expr, scope.locale.as_deref());
+        }
+        scope
+    }
+
+    /// Visits a component wrapped by withTranslation binding t of its props to the scope of the HOC
+    /// The HOC is unwrapped unless t is left for runtime
+    fn visit_mut_wrapped<N>(&mut self, id: Id, param: Option<Pat>, n: &mut N)
+    where
+        N: VisitMutWith<Self> + for<'a> VisitWith<References<'a>>,
+    {
+        let Some(scope) = self.wrapped.get(&id).cloned() else {
+            return n.visit_mut_children_with(self);
+        };
+        let props = match &param {
+            // e.g) (props) => props.t("foo")
+            Some(Pat::Ident(binding)) => Some(binding.id.to_id()),
+            // e.g) ({ t }) => t("foo")
+            Some(pat) => {
+                for id in translation_bindings(pat, &self.method_names) {
+                    self.translation_ids.insert(id, Some(scope.clone()));
+                }
+                None
+            }
+            None => None,
+        };
+        let outer = self.props_scope.replace((scope, props.clone()));
         n.visit_mut_children_with(self);
-        // mark import statement for react-i18next as an invalid node
-        // e.g) import { useTranslation } from "react-i18next";
-        if let ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) = &*n {
-            if &*decl.src.value == TRANSLATION_PACKAGE_NAME {
-                n.take();
+        self.props_scope = outer;
+
+        let ids = self.translation_ids.keys().cloned().collect();
+        let mut references = References {
+            ids: &ids,
+            method_names: &self.method_names,
+            props: props.as_ref(),
+            found: false,
+        };
+        n.visit_with(&mut references);
+        // keep the HOC for t left in the component
+        if !references.found {
+            self.unwrapped.insert(id);
+        }
+    }
+
+    /// Returns a body of <Translation /> to replace it with once t in it is inlined
+    fn unwrap_render_prop(&self, n: &JSXElement) -> Option<Box<Expr>> {
+        match &n.opening.name {
+            JSXElementName::Ident(ident) if self.render_prop_names.contains(&*ident.sym) => {
+                render_prop_body(n, &self.method_names)
             }
+            _ => None,
         }
     }
 
-    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
-        stmts.visit_mut_children_with(self);
-        // remove invalid import statement
-        stmts.retain(|s| !matches!(s, Stmt::Empty(..)));
+    /// Loads catalogs of namespaces in the locale and returns the loaded ones
+    /// e.g) "foo", ["foo", "bar"]
+    fn load_namespaces(&mut self, expr: &Expr, locale: Option<&str>) -> Vec<String> {
+        let namespaces: Vec<&Str> = match expr {
+            Expr::Lit(Lit::Str(lit)) => vec![lit],
+            Expr::Array(lit) => lit
+                .elems
+                .iter()
+                .flatten()
+                .filter_map(|el| match &*el.expr {
+                    Expr::Lit(Lit::Str(lit)) => Some(lit),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let namespaces = namespaces
+            .into_iter()
+            .map(|namespace| (namespace.value.to_string(), namespace.span))
+            .collect();
+        self.load_catalogs(namespaces, locale)
     }
 
-    fn visit_mut_stmt(&mut self, s: &mut Stmt) {
-        s.visit_mut_children_with(self);
+    fn load_catalogs(
+        &mut self,
+        namespaces: Vec<(String, Span)>,
+        locale: Option<&str>,
+    ) -> Vec<String> {
+        // catalogs of lng are loaded instead of the locale of config
+        self.set_locale(locale);
+        let mut loaded = vec![];
+        for (namespace, span) in namespaces {
+            match self.cache.add_file(namespace.clone()) {
+                Ok(_) => loaded.push(namespace),
+                Err(error) => emit_error(&error.to_string(), span),
+            }
+        }
+        self.set_locale(None);
+        loaded
+    }
 
-        if let Stmt::Decl(Decl::Var(var)) = &*s {
-            if let Some(decl) = var.decls.get(0) {
-                if let Some(ref init) = decl.init {
-                    if let Expr::Call(call_expr) = &**init {
-                        if let Callee::Expr(callee_expr) = &call_expr.callee {
-                            if let Expr::Ident(ident) = &**callee_expr {
-                                if ident.sym.to_string() == TRANSLATION_HOOKS_NAME {
-                                    // mark declarations for react-i18next as an invalid node
-                                    // e.g) const { t } = useTranslation("xxx");
-                                    s.take();
-                                }
-                            }
+    /// Applies ns and lng of options of t() to the scope as i18next does
+    /// e.g) t("foo", { ns: "common" }), t("foo", { lng: "de" })
+    fn options_scope(&mut self, mut scope: Scope, options: Option<&ExprOrSpread>) -> Scope {
+        let Some(Expr::Object(options)) = options.map(|arg| &*arg.expr) else {
+            return scope;
+        };
+        let props = props_from_object(options);
+        if let Some(lng) = props.get("lng") {
+            match &**lng {
+                Expr::Lit(Lit::Str(lng)) => scope.locale = Some(lng.value.to_string()),
+                _ => emit_error("lng must be a string literal", lng.span()),
+            }
+        }
+        if let Some(ns) = props.get("ns") {
+            if !matches!(&**ns, Expr::Lit(Lit::Str(_)) | Expr::Array(_)) {
+                emit_error("ns must be a string literal", ns.span());
+            }
+            scope.namespaces = self.load_namespaces(ns, scope.locale.as_deref());
+        } else if props.contains_key("lng") {
+            // catalogs of the namespaces are loaded again in lng
+            let namespaces = self
+                .namespaces_or_default(&scope.namespaces)
+                .iter()
+                .map(|namespace| (namespace.clone(), options.span))
+                .collect();
+            scope.namespaces = self.load_catalogs(namespaces, scope.locale.as_deref());
+        }
+        scope
+    }
+
+    /// Returns a scope of arguments of getFixedT(lng, ns, keyPrefix)
+    /// e.g) i18next.getFixedT(null, "common"), i18next.getFixedT("de", ["admin", "common"], "form")
+    fn fixed_t_scope(&mut self, args: &[ExprOrSpread]) -> Scope {
+        let mut scope = Scope::default();
+        let literal = |index: usize, name: &str| match args.get(index).map(|arg| &*arg.expr) {
+            Some(Expr::Lit(Lit::Str(lit))) => Some(lit.value.to_string()),
+            // e.g) null or undefined for the default
+            None | Some(Expr::Lit(Lit::Null(_))) => None,
+            Some(Expr::Ident(ident)) if &ident.sym == "undefined" => None,
+            Some(expr) => {
+                emit_error(&format!("{} must be a string literal", name), expr.span());
+                None
+            }
+        };
+        scope.locale = literal(0, "lng");
+        scope.key_prefix = literal(2, "keyPrefix");
+        if let Some(arg) = args.get(1) {
+            scope.namespaces = self.load_namespaces(&arg.expr, scope.locale.as_deref());
+        }
+        scope
+    }
+
+    /// Registers aliases of imports
+    fn register_import(&mut self, n: &ImportDecl) {
+        // e.g) import i18next from "i18next", import { i18n } from "./i18n"
+        for specifier in &n.specifiers {
+            let local = match specifier {
+                ImportSpecifier::Named(named) => &named.local,
+                ImportSpecifier::Default(default) => &default.local,
+                ImportSpecifier::Namespace(namespace) => &namespace.local,
+            };
+            if self.instance_names.contains(&*local.sym) {
+                self.instance_ids.insert(local.to_id());
+            }
+        }
+        if !self.package_names.iter().any(|name| *name == *n.src.value) {
+            return;
+        }
+        // register aliases of imports e.g) import { useTranslation as useT } from "react-i18next"
+        for specifier in &n.specifiers {
+            let ImportSpecifier::Named(named) = specifier else {
+                continue;
+            };
+            let imported = match &named.imported {
+                Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                Some(ModuleExportName::Str(str)) => str.value.to_string(),
+                None => named.local.sym.to_string(),
+            };
+            self.register_alias(&imported, &named.local);
+        }
+    }
+
+    /// Registers aliases of require e.g) const { useTranslation: useT } = require("react-i18next")
+    fn register_require(&mut self, stmt: &Stmt) {
+        let Stmt::Decl(Decl::Var(var)) = stmt else {
+            return;
+        };
+        for decl in &var.decls {
+            let Some(Expr::Call(call_expr)) = decl.init.as_deref() else {
+                continue;
+            };
+            let Callee::Expr(callee) = &call_expr.callee else {
+                continue;
+            };
+            if !matches!(&**callee, Expr::Ident(ident) if &ident.sym == "require") {
+                continue;
+            }
+            // e.g) const i18next = require("i18next")
+            if let Pat::Ident(binding) = &decl.name {
+                if self.instance_names.contains(&*binding.id.sym) {
+                    self.instance_ids.insert(binding.id.to_id());
+                }
+            }
+            let Pat::Object(obj) = &decl.name else {
+                continue;
+            };
+            if self.require_src(call_expr).is_none() {
+                continue;
+            }
+            for prop in &obj.props {
+                match prop {
+                    ObjectPatProp::Assign(assign) => {
+                        self.register_alias(&assign.key.id.sym, &assign.key.id)
+                    }
+                    ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                        if let (PropName::Ident(key), Pat::Ident(local)) = (key, &**value) {
+                            self.register_alias(&key.sym, &local.id);
                         }
                     }
+                    ObjectPatProp::Rest(_) => {}
                 }
             }
         }
     }
 
+    fn register_alias(&mut self, imported: &str, local: &Ident) {
+        let name = local.sym.to_string();
+        if self.method_names.contains(imported) {
+            // e.g) import { t } from "i18next"
+            self.translation_ids.insert(local.to_id(), None);
+        } else if self.hook_names.contains(imported) {
+            self.hook_names.insert(name);
+        } else if self.component_names.contains(imported) {
+            self.component_names.insert(name);
+        } else if self.hoc_names.contains(imported) {
+            self.hoc_names.insert(name);
+        } else if self.render_prop_names.contains(imported) {
+            self.render_prop_names.insert(name);
+        }
+    }
+
+    /// Reports keys served from fallback locales so that untranslated keys are visible in the build
+    fn report_fallbacks(&self, span: Span) {
+        let fallbacks = self.cache.take_fallbacks();
+        for fallback in &fallbacks {
+            emit_warning(
+                &format!(
+                    "key: {}:{} is missing in {} and served from {}",
+                    fallback.namespace, fallback.key, self.locale, fallback.locale
+                ),
+                span,
+            );
+        }
+        let Some(report) = self.fallback_report.as_deref() else {
+            return;
+        };
+        if fallbacks.is_empty() {
+            return;
+        }
+        if let Err(error) = write_fallbacks(report, &self.locale, &fallbacks) {
+            emit_warning(
+                &format!("failed to write {}: {}", report.display(), error),
+                span,
+            );
+        }
+    }
+}
+
+impl VisitMut for TransformVisitor {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        self.declared = collect_decls(&*n).into_iter().collect();
+        // register aliases before components wrapped by them are collected
+        for item in &n.body {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => self.register_import(decl),
+                ModuleItem::Stmt(stmt) => self.register_require(stmt),
+                _ => {}
+            }
+        }
+        for (id, args) in wrapped_components(&*n, &self.hoc_names) {
+            let scope = self.hook_scope(&args, None);
+            self.wrapped.insert(id, scope);
+        }
+        n.visit_mut_children_with(self);
+        n.visit_mut_with(&mut Unwrapper {
+            hoc_names: &self.hoc_names,
+            components: &self.unwrapped,
+        });
+        self.cleanup(n);
+        if contains_ident_ref(&*n, &self.escape.to_id()) {
+            // after imports and directives e.g) "use client"
+            let index = n
+                .body
+                .iter()
+                .position(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(_)) => false,
+                    ModuleItem::Stmt(stmt) => !is_directive(stmt),
+                    _ => true,
+                })
+                .unwrap_or(n.body.len());
+            n.body.insert(index, ModuleItem::Stmt(self.escape_helper()));
+        }
+    }
+
+    fn visit_mut_script(&mut self, n: &mut Script) {
+        self.declared = collect_decls(&*n).into_iter().collect();
+        for stmt in &n.body {
+            self.register_require(stmt);
+        }
+        for (id, args) in wrapped_components(&*n, &self.hoc_names) {
+            let scope = self.hook_scope(&args, None);
+            self.wrapped.insert(id, scope);
+        }
+        n.visit_mut_children_with(self);
+        n.visit_mut_with(&mut Unwrapper {
+            hoc_names: &self.hoc_names,
+            components: &self.unwrapped,
+        });
+        self.cleanup(n);
+        if contains_ident_ref(&*n, &self.escape.to_id()) {
+            let index = n
+                .body
+                .iter()
+                .position(|stmt| !is_directive(stmt))
+                .unwrap_or(n.body.len());
+            n.body.insert(index, self.escape_helper());
+        }
+    }
+
+    fn visit_mut_class_decl(&mut self, n: &mut ClassDecl) {
+        // e.g) class Component extends React.Component { render() { return this.props.t("foo"); } }
+        self.visit_mut_wrapped(n.ident.to_id(), None, n);
+    }
+
+    fn visit_mut_fn_decl(&mut self, n: &mut FnDecl) {
+        // e.g) function Component({ t }) { return t("foo"); }
+        let param = n.function.params.first().map(|param| param.pat.clone());
+        self.visit_mut_wrapped(n.ident.to_id(), param, n);
+    }
+
+    fn visit_mut_var_declarator(&mut self, n: &mut VarDeclarator) {
+        // e.g) const Component = (props) => props.t("foo");
+        let Pat::Ident(binding) = &n.name else {
+            return n.visit_mut_children_with(self);
+        };
+        let param = match n.init.as_deref() {
+            Some(Expr::Arrow(arrow)) => arrow.params.first().cloned(),
+            Some(Expr::Fn(f)) => f.function.params.first().map(|param| param.pat.clone()),
+            _ => None,
+        };
+        self.visit_mut_wrapped(binding.id.to_id(), param, n);
+    }
+
+    fn visit_mut_function(&mut self, n: &mut Function) {
+        let depth = self.scopes.len();
+        n.visit_mut_children_with(self);
+        // pop namespaces of useTranslation in the function
+        self.scopes.truncate(depth);
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        let depth = self.scopes.len();
+        n.visit_mut_children_with(self);
+        // pop namespaces of useTranslation in the function
+        self.scopes.truncate(depth);
+    }
+
     fn visit_mut_var_decl(&mut self, n: &mut VarDecl) {
-        if let Some(decl) = n.decls.get(0) {
+        // e.g) const x = 1, { t } = useTranslation("foo")
+        for decl in &n.decls {
             if let Some(ref init) = decl.init {
+                // e.g) const { t } = await getTranslation(lng, "foo"), const t = await getTranslations("foo")
+                if let Some((call_expr, lng_index)) = self.async_hook_call(init) {
+                    let mut args = call_expr.args.clone();
+                    let lng = lng_index
+                        .filter(|index| *index < args.len())
+                        .map(|index| args.remove(index));
+                    // lng given at runtime is inlined in the locale of config
+                    let locale = match lng.as_ref().map(|lng| &*lng.expr) {
+                        Some(Expr::Lit(Lit::Str(lng))) => Some(lng.value.to_string()),
+                        _ => None,
+                    };
+                    let scope = self.hook_scope(&args, locale);
+                    let ids = match &decl.name {
+                        Pat::Ident(binding) => vec![binding.id.to_id()],
+                        pat => translation_bindings(pat, &self.method_names),
+                    };
+                    for id in ids {
+                        self.translation_ids.insert(id, Some(scope.clone()));
+                    }
+                    self.scopes.push(scope);
+                }
                 if let Expr::Call(call_expr) = &**init {
                     if let Callee::Expr(callee_expr) = &call_expr.callee {
                         if let Expr::Ident(ident) = &**callee_expr {
-                            if ident.sym.to_string() == TRANSLATION_HOOKS_NAME {
+                            if self.hook_names.contains(&*ident.sym) {
                                 // store args of useTranslation("foo") as scopes and load json files for i18n
-                                self.scopes = vec![];
-                                if let Some(arg) = call_expr.args.get(0) {
-                                    if let Expr::Array(lit) = &*arg.expr {
-                                        // e.g) useTranslation(["foo", "bar"])
-                                        for el in &lit.elems {
-                                            let Some(el) = el else { return; };
-                                            if let Expr::Lit(Lit::Str(Str {
-                                                span,
-                                                raw: _,
-                                                value,
-                                            })) = &*el.expr
-                                            {
-                                                match self.cache.add_file(value.to_string()) {
-                                                    Ok(_) => self.scopes.push(value.to_string()),
-                                                    Err(error) => {
-                                                        emit_error(&error.to_string(), *span);
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    if let Expr::Lit(Lit::Str(Str {
-                                        value,
-                                        span,
-                                        raw: _,
-                                    })) = &*arg.expr
-                                    {
-                                        // e.g) useTranslation("foo")
-                                        match self.cache.add_file(value.to_string()) {
-                                            Ok(_) => self.scopes.push(value.to_string()),
-                                            Err(error) => {
-                                                emit_error(&error.to_string(), *span);
-                                            }
-                                        }
-                                    }
+                                let scope = self.hook_scope(&call_expr.args, None);
+                                // track bindings of t by their ids e.g) const { t: translate } = useTranslation()
+                                for id in translation_bindings(&decl.name, &self.method_names) {
+                                    self.translation_ids.insert(id, Some(scope.clone()));
+                                }
+                                // the scope lasts until the end of the enclosing function
+                                self.scopes.push(scope);
+                            }
+                        }
+                        // e.g) const t = i18next.getFixedT(null, "foo", "bar")
+                        if let Expr::Member(MemberExpr {
+                            obj,
+                            prop: MemberProp::Ident(prop),
+                            ..
+                        }) = &**callee_expr
+                        {
+                            if &prop.sym == "getFixedT" && self.is_instance(obj) {
+                                if let Pat::Ident(binding) = &decl.name {
+                                    let scope = self.fixed_t_scope(&call_expr.args);
+                                    self.translation_ids.insert(binding.id.to_id(), Some(scope));
                                 }
                             }
                         }
                     }
                 }
+                // e.g) const { t } = this.props in a component wrapped by withTranslation
+                if let Some((scope, props)) = &self.props_scope {
+                    if is_props(init, props.as_ref()) {
+                        for id in translation_bindings(&decl.name, &self.method_names) {
+                            self.translation_ids.insert(id, Some(scope.clone()));
+                        }
+                    }
+                }
             }
         }
 
@@ -149,16 +764,57 @@ impl VisitMut for TransformVisitor {
 
     fn visit_mut_jsx_element(&mut self, n: &mut JSXElement) {
         if let JSXElementName::Ident(ident) = &n.opening.name {
-            if ident.sym.to_string() == TRANSLATION_COMPONENT_NAME {
+            if self.render_prop_names.contains(&*ident.sym) {
+                // e.g) <Translation ns="foo" keyPrefix="bar">{(t) => t("baz")}</Translation>
+                let mut scope = Scope::default();
+                for attr in &n.opening.attrs {
+                    let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                        continue;
+                    };
+                    let JSXAttrName::Ident(name) = &attr.name else {
+                        continue;
+                    };
+                    if name.sym == "ns" {
+                        if let Some(ns) = jsx_attr_expr(&attr.value) {
+                            scope.namespaces = self.load_namespaces(&ns, None);
+                        }
+                    } else if name.sym == "keyPrefix" {
+                        scope.key_prefix = jsx_attr_str(&attr.value);
+                    }
+                }
+                if let Some(Pat::Ident(t)) = render_prop(n).and_then(|arrow| arrow.params.first()) {
+                    self.translation_ids.insert(t.id.to_id(), Some(scope));
+                }
+                n.visit_mut_children_with(self);
+                return;
+            }
+            if self.component_names.contains(&*ident.sym) {
+                let mut component_map = HashMap::new();
+                let mut i18n_key = None;
+                let mut ns = None;
+                let mut values = None;
+                let mut count = None;
                 for attr in &mut n.opening.attrs.to_vec() {
                     if let JSXAttrOrSpread::JSXAttr(attr) = attr {
                         if let JSXAttrName::Ident(ident) = &mut attr.name {
-                            if ident.sym.to_string() == "i18nKey" {
-                                // do not support i18nKey so far
-                                if let Some(JSXAttrValue::Lit(Lit::Str(_))) = &attr.value {
-                                    unimplemented!("i18nKey has not been supported");
+                            if ident.sym == "i18nKey" {
+                                // e.g) <Trans i18nKey="foo" />
+                                match jsx_attr_str(&attr.value) {
+                                    Some(key) => i18n_key = Some((key, attr.span)),
+                                    None => {
+                                        emit_error("i18nKey must be a string literal", attr.span)
+                                    }
                                 }
-                            } else if ident.sym.to_string() == TRANSLATION_COMPONENT_ATTR_NAME {
+                            } else if ident.sym == "ns" {
+                                // e.g) <Trans ns="foo" i18nKey="bar" />
+                                ns = jsx_attr_str(&attr.value);
+                            } else if ident.sym == "values" {
+                                // e.g) <Trans values={{ name }} i18nKey="bar" />
+                                values = jsx_attr_expr(&attr.value);
+                            } else if ident.sym == "count" {
+                                // e.g) <Trans count={count} i18nKey="bar" />
+                                count = jsx_attr_expr(&attr.value);
+                            } else if *ident.sym == self.components_attr_name {
                                 if let Some(JSXAttrValue::JSXExprContainer(c)) = &mut attr.value {
                                     if let JSXExpr::Expr(expr) = &mut c.expr {
                                         if let Expr::Object(ObjectLit { span: _, props }) =
@@ -176,7 +832,7 @@ impl VisitMut for TransformVisitor {
                                                         if let Expr::JSXElement(element) = &**value
                                                         {
                                                             if let PropName::Ident(ident) = &key {
-                                                                self.component_map.insert(
+                                                                component_map.insert(
                                                                     ident.sym.to_string(),
                                                                     *element.clone(),
                                                                 );
@@ -188,7 +844,7 @@ impl VisitMut for TransformVisitor {
                                                             {
                                                                 if let PropName::Ident(ident) = &key
                                                                 {
-                                                                    self.component_map.insert(
+                                                                    component_map.insert(
                                                                         ident.sym.to_string(),
                                                                         *element.clone(),
                                                                     );
@@ -198,6 +854,21 @@ impl VisitMut for TransformVisitor {
                                                     }
                                                 }
                                             }
+                                        } else if let Expr::Array(array) = &**expr {
+                                            // e.g) components={[<b />, <Link />]} for <0>, <1>
+                                            for (index, elem) in array.elems.iter().enumerate() {
+                                                let Some(ExprOrSpread { spread: None, expr }) =
+                                                    elem
+                                                else {
+                                                    continue;
+                                                };
+                                                if let Expr::JSXElement(element) = &**expr {
+                                                    component_map.insert(
+                                                        index.to_string(),
+                                                        *element.clone(),
+                                                    );
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -205,6 +876,76 @@ impl VisitMut for TransformVisitor {
                         }
                     }
                 }
+
+                if let Some((key, span)) = i18n_key {
+                    // replace children of <Trans /> with the message of i18nKey
+                    let scopes = match ns {
+                        Some(ns) => vec![ns],
+                        None => self.current_scope().namespaces,
+                    };
+                    // keyPrefix and lng of useTranslation are not applied to <Trans /> as react-i18next
+                    let (filename, path) = json_path_from_key(
+                        key.clone(),
+                        self.namespaces_or_default(&scopes),
+                        None,
+                        self.ns_separator.as_deref(),
+                        self.key_separator.as_deref(),
+                    );
+                    // e.g) <Trans i18nKey="foo:bar" /> refers to a namespace out of useTranslation
+                    if let Err(error) = self.cache.add_file(filename.clone()) {
+                        emit_error(&error.to_string(), span);
+                    }
+                    let attrs = TransAttrs {
+                        components: component_map,
+                        values,
+                        count,
+                    };
+                    let node = match self.make_trans_node(&filename, &path, &key, &attrs, span) {
+                        Ok(Some(node)) => Some(node),
+                        Ok(None) => {
+                            if self.missing_key_mode(&filename).keeps_runtime() {
+                                self.runtime_elements.insert(n.span);
+                            }
+                            // e.g) <Trans i18nKey="foo">default</Trans>
+                            let default = (!n.children.is_empty()).then(|| {
+                                Box::new(Expr::JSXFragment(JSXFragment {
+                                    span: DUMMY_SP,
+                                    opening: JSXOpeningFragment { span: DUMMY_SP },
+                                    children: n.children.clone(),
+                                    closing: JSXClosingFragment { span: DUMMY_SP },
+                                }))
+                            });
+                            self.missing_key(&filename, &path, &key, default, span)
+                        }
+                        Err(error) => {
+                            emit_error(&error, span);
+                            None
+                        }
+                    };
+                    if let Some(node) = node {
+                        n.children = match *node {
+                            Expr::JSXFragment(fragment) => fragment.children,
+                            expr => {
+                                vec![JSXElementChild::JSXExprContainer(JSXExprContainer {
+                                    span: DUMMY_SP,
+                                    expr: JSXExpr::Expr(Box::new(expr)),
+                                })]
+                            }
+                        };
+                        n.opening.self_closing = false;
+                        n.closing = Some(JSXClosingElement {
+                            span: DUMMY_SP,
+                            name: n.opening.name.clone(),
+                        });
+                    }
+                    self.report_fallbacks(span);
+                    component_map = attrs.components;
+                }
+                // e.g) <Trans components={{ bold: <b /> }}>{t("foo")}</Trans>
+                self.component_maps.push(component_map);
+                n.visit_mut_children_with(self);
+                self.component_maps.pop();
+                return;
             }
         }
         n.visit_mut_children_with(self);
@@ -214,6 +955,7 @@ impl VisitMut for TransformVisitor {
         if let Expr::Call(call_expr) = &mut *n {
             // NOTE: using visit_mut_expr for replacing call_expr to literal
             if let Callee::Expr(expr) = &mut call_expr.callee {
+                let mut scope = None;
                 if let Expr::Ident(id) = &mut **expr {
                     if (&id.sym == "useEffect" || &id.sym == "useCallback" || &id.sym == "useMemo")
                         && call_expr.args.len() > 1
@@ -226,120 +968,109 @@ impl VisitMut for TransformVisitor {
                                 .iter()
                                 .filter(|&el| {
                                     let Some(el) = el else { return false };
-                                    let Expr::Ident(id) = &*el.expr else { return true };
-                                    // NOTE: using string but it's better to use ident instead
-                                    &*id.sym != "t"
+                                    let Expr::Ident(id) = &*el.expr else {
+                                        return true;
+                                    };
+                                    !self.translation_ids.contains_key(&id.to_id())
                                 })
                                 .cloned()
                                 .collect();
-                            call_expr.args[1].expr = Box::new(Expr::Array(ArrayLit {
+                            *call_expr.args[1].expr = Expr::Array(ArrayLit {
                                 span: DUMMY_SP,
                                 elems: deps,
-                            }))
+                            })
                         };
                     }
 
-                    if &id.sym == TRANSLATION_METHOD_NAME {
-                        match call_expr.args.len() {
-                            // t("foo", { bar: "a" })
-                            n if n > 1 => {
-                                if let Some(Lit::Str(lit)) = call_expr.args[0].expr.as_lit() {
-                                    let (filename, path) =
-                                        json_path_from_key(lit.value.to_string(), &self.scopes);
-                                    if let Ok(value) = self.cache.get(filename, path) {
-                                        let message = format!("`{}`", value)
-                                            .replace("{{", "${")
-                                            .replace("}}", "}");
-
-                                        if let Some(ExprOrSpread {
-                                            expr: id_or_obj,
-                                            spread: _,
-                                        }) = &call_expr.args.get(1)
-                                        {
-                                            if let Expr::Ident(ident) = &**id_or_obj {
-                                                match self.make_interporation_node(
-                                                    message,
-                                                    ExprOrSpread {
-                                                        spread: None,
-                                                        expr: Box::new(Expr::Ident(ident.clone())),
-                                                    },
-                                                ) {
-                                                    Ok(node) => {
-                                                        *call_expr = node;
-                                                    }
-                                                    Err(_) => {
-                                                        emit_error(
-                                                            &format!(
-                                                            "failed to make node from {} for {}",
-                                                            value, lit.value
-                                                        ),
-                                                            lit.span,
-                                                        );
-                                                    }
-                                                }
-                                            } else if let Expr::Object(obj) = &**id_or_obj {
-                                                match self.make_interporation_node(
-                                                    message,
-                                                    ExprOrSpread {
-                                                        spread: None,
-                                                        expr: Box::new(Expr::Object(obj.clone())),
-                                                    },
-                                                ) {
-                                                    Ok(node) => {
-                                                        *call_expr = node;
-                                                    }
-                                                    Err(_) => {
-                                                        emit_error(
-                                                            &format!(
-                                                            "failed to make node from {} for {}",
-                                                            value, lit.value
-                                                        ),
-                                                            lit.span,
-                                                        );
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        emit_error(
-                                            &format!("key: {} not found", lit.value),
-                                            lit.span,
-                                        );
+                    scope = self
+                        .translation_ids
+                        .get(&id.to_id())
+                        .map(|scope| scope.clone().unwrap_or_else(|| self.current_scope()));
+                    if scope.is_none()
+                        && self.method_names.contains(&*id.sym)
+                        && !self.declared.contains(&id.to_id())
+                    {
+                        emit_error(
+                            &format!("{} is used out of scope of useTranslation", id.sym),
+                            id.span,
+                        );
+                    }
+                }
+                // e.g) i18next.t("foo:bar"), i18n.t("foo:bar")
+                if let Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Ident(prop),
+                    ..
+                }) = &**expr
+                {
+                    if self.is_instance(obj) && self.method_names.contains(&*prop.sym) {
+                        scope = Some(Scope::default());
+                    }
+                    // e.g) this.props.t("foo") in a component wrapped by withTranslation
+                    if let Some((props_scope, props)) = &self.props_scope {
+                        if is_props(obj, props.as_ref()) && self.method_names.contains(&*prop.sym) {
+                            scope = Some(props_scope.clone());
+                        }
+                    }
+                }
+                if let Some(scope) = scope {
+                    // e.g) t("foo", { ns: "common" }), t("foo", "default", { lng: "de" })
+                    let options = match call_expr.args.get(1).map(|arg| &*arg.expr) {
+                        Some(Expr::Lit(Lit::Str(_))) => call_expr.args.get(2),
+                        _ => call_expr.args.get(1),
+                    };
+                    let scope = self.options_scope(scope, options);
+                    self.set_locale(scope.locale.as_deref());
+                    match call_expr.args.len() {
+                        len if len > 1 => {
+                            if let Some(Lit::Str(lit)) = call_expr.args[0].expr.as_lit() {
+                                let lit = lit.clone();
+                                let node = match (&*call_expr.args[1].expr, call_expr.args.get(2)) {
+                                    // t("foo", "default")
+                                    (Expr::Lit(Lit::Str(default)), None) => self.make_message_node(
+                                        &lit,
+                                        &scope,
+                                        Some(Box::new(default.clone().into())),
+                                    ),
+                                    // t("foo", "default", { bar: "a" })
+                                    (Expr::Lit(Lit::Str(default)), Some(options))
+                                        if is_options(options) =>
+                                    {
+                                        let default = Some(Box::new(default.clone().into()));
+                                        self.make_translation_node(
+                                            &lit,
+                                            &scope,
+                                            default,
+                                            options.clone(),
+                                        )
                                     }
+                                    // t("foo", { bar: "a" }), t("foo", props.options)
+                                    _ if is_options(&call_expr.args[1]) => {
+                                        let options = call_expr.args[1].clone();
+                                        self.make_translation_node(&lit, &scope, None, options)
+                                    }
+                                    _ => None,
+                                };
+                                self.report_fallbacks(lit.span);
+                                if let Some(node) = node {
+                                    *n = *node;
                                 }
                             }
-                            // t("foo")
-                            1 => {
-                                if let Some(Lit::Str(lit)) = call_expr.args[0].expr.as_mut_lit() {
-                                    let (filename, path) =
-                                        json_path_from_key(lit.value.to_string(), &self.scopes);
-                                    if let Ok(value) = self.cache.get(filename, path) {
-                                        let node = self.make_component_interporation_node(
-                                            &value,
-                                            &self.component_map,
-                                        );
-                                        if let Ok(node) = node {
-                                            *n = *node;
-                                        } else {
-                                            emit_error(
-                                                &format!(
-                                                    "failed to make node from {} for {}",
-                                                    value, lit.value
-                                                ),
-                                                lit.span,
-                                            );
-                                        }
-                                    } else {
-                                        emit_error(
-                                            &format!("key: {} not found", lit.value),
-                                            lit.span,
-                                        );
-                                    }
+                        }
+                        // t("foo")
+                        1 => {
+                            if let Some(Lit::Str(lit)) = call_expr.args[0].expr.as_lit() {
+                                let lit = lit.clone();
+                                let node = self.make_message_node(&lit, &scope, None);
+                                self.report_fallbacks(lit.span);
+                                if let Some(node) = node {
+                                    *n = *node;
                                 }
                             }
-                            _ => {}
                         }
+                        _ => {}
                     }
+                    self.set_locale(None);
                 }
             }
         }
@@ -347,8 +1078,14 @@ impl VisitMut for TransformVisitor {
         n.visit_mut_children_with(self);
 
         if let Expr::JSXElement(jsx) = &mut *n {
+            if let Some(body) = self.unwrap_render_prop(jsx) {
+                *n = *body;
+                return;
+            }
             if let JSXElementName::Ident(ident) = &jsx.opening.name {
-                if ident.sym.to_string() == TRANSLATION_COMPONENT_NAME {
+                if self.component_names.contains(&*ident.sym)
+                    && !self.runtime_elements.contains(&jsx.span)
+                {
                     *n = Expr::JSXFragment(JSXFragment {
                         span: DUMMY_SP,
                         opening: JSXOpeningFragment { span: DUMMY_SP },
@@ -363,8 +1100,21 @@ impl VisitMut for TransformVisitor {
     fn visit_mut_jsx_element_child(&mut self, n: &mut JSXElementChild) {
         n.visit_mut_children_with(self);
         if let JSXElementChild::JSXElement(jsx) = n {
+            if let Some(body) = self.unwrap_render_prop(jsx) {
+                *n = match *body {
+                    Expr::JSXElement(element) => JSXElementChild::JSXElement(element),
+                    Expr::JSXFragment(fragment) => JSXElementChild::JSXFragment(fragment),
+                    expr => JSXElementChild::JSXExprContainer(JSXExprContainer {
+                        span: DUMMY_SP,
+                        expr: JSXExpr::Expr(Box::new(expr)),
+                    }),
+                };
+                return;
+            }
             if let JSXElementName::Ident(ident) = &jsx.opening.name {
-                if ident.sym.to_string() == TRANSLATION_COMPONENT_NAME {
+                if self.component_names.contains(&*ident.sym)
+                    && !self.runtime_elements.contains(&jsx.span)
+                {
                     *n = JSXElementChild::JSXFragment(JSXFragment {
                         span: DUMMY_SP,
                         opening: JSXOpeningFragment { span: DUMMY_SP },
@@ -378,106 +1128,914 @@ impl VisitMut for TransformVisitor {
 }
 
 impl TransformVisitor {
-    pub fn make_component_interporation_node(
+    /// Makes a node of a message replacing its tags with components without evaluating the message
+    /// e.g) "Hello <bold>{{name}}</bold>" -> <>Hello <strong>{name}</strong></>, "hello" -> "hello"
+    /// placeholders are kept as texts without values e.g) t("foo") for "{{name}}"
+    fn make_component_interporation_node(
         &self,
         message: &str,
         map: &HashMap<String, JSXElement>,
-    ) -> Result<Box<Expr>, &str> {
-        let mut n = parse_code(format!("<>{}</>", message));
-        if let Ok(node) = &mut n {
-            if let Expr::JSXFragment(fragment) = &mut **node {
-                let mut has_element = false;
-                for child in &mut fragment.children {
-                    if let JSXElementChild::JSXElement(c) = child {
-                        has_element = true;
-                        if let JSXElementName::Ident(ident) = &c.opening.name {
-                            if let Some(value) = map.get(&ident.sym.to_string()) {
-                                let children = c.children.clone();
-                                let has_children = !children.is_empty();
-                                let node = JSXElement {
-                                    span: DUMMY_SP,
-                                    opening: JSXOpeningElement {
-                                        span: DUMMY_SP,
-                                        self_closing: !has_children,
-                                        ..value.opening.clone()
-                                    },
-                                    children,
-                                    closing: if !has_children {
-                                        None
-                                    } else {
-                                        Some(JSXClosingElement {
-                                            span: DUMMY_SP,
-                                            name: value.opening.name.clone(),
-                                        })
-                                    },
-                                };
-                                *child = JSXElementChild::JSXElement(Box::new(node));
-                            }
+        values: Option<&TransValues>,
+        span: Span,
+    ) -> Result<Box<Expr>, String> {
+        let nodes = parse_message(message)?;
+        let children = self.make_jsx_children(nodes, message, map, values, span)?;
+        let mut text = String::new();
+        let is_text = children.iter().all(|child| match child {
+            JSXElementChild::JSXText(child) => {
+                text.push_str(&child.value);
+                true
+            }
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            }) => match &**expr {
+                Expr::Lit(Lit::Str(child)) => {
+                    text.push_str(&child.value);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        });
+        if is_text {
+            return Ok(Box::new(Expr::Lit(Lit::Str(text.into()))));
+        }
+        Ok(Box::new(Expr::JSXFragment(JSXFragment {
+            span: DUMMY_SP,
+            opening: JSXOpeningFragment { span: DUMMY_SP },
+            children,
+            closing: JSXClosingFragment { span: DUMMY_SP },
+        })))
+    }
+
+    /// Makes children of nodes of a message e.g) [Text("Hello "), Element("bold", ...)]
+    fn make_jsx_children(
+        &self,
+        nodes: Vec<MessageNode>,
+        message: &str,
+        map: &HashMap<String, JSXElement>,
+        values: Option<&TransValues>,
+        span: Span,
+    ) -> Result<Vec<JSXElementChild>, String> {
+        let mut children = vec![];
+        for node in nodes {
+            match node {
+                MessageNode::Segment(Segment::Text(text)) => children.push(jsx_text(&text)),
+                MessageNode::Segment(Segment::Placeholder { name, formats, raw }) => {
+                    let path = self.placeholder_path(&name);
+                    let Some(mut expr) = values.and_then(|values| values.resolve(&path)) else {
+                        // keeps the placeholder as i18next does for missing values
+                        let placeholder = format!(
+                            "{{{{{}{}}}}}",
+                            if raw { "- " } else { "" },
+                            std::iter::once(name.as_str())
+                                .chain(formats.iter().map(|format| format.as_str()))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        );
+                        if values.is_some() {
+                            emit_warning(
+                                &format!("{} of <Trans /> has no value", placeholder),
+                                span,
+                            );
                         }
+                        children.push(jsx_text(&placeholder));
+                        continue;
+                    };
+                    // React escapes values, so {{- html}} is the same as {{html}}
+                    for format in formats {
+                        expr = Format::parse(&format)?.to_expr(*expr, &self.locale);
                     }
+                    children.push(JSXElementChild::JSXExprContainer(JSXExprContainer {
+                        span: DUMMY_SP,
+                        expr: JSXExpr::Expr(expr),
+                    }));
                 }
-                if !has_element {
-                    return Ok(Box::new(Expr::Lit(Lit::Str(Str {
+                MessageNode::Element {
+                    name,
+                    children: nodes,
+                } => {
+                    let inner = self.make_jsx_children(nodes, message, map, values, span)?;
+                    if let Some(component) = map.get(&name) {
+                        let element = jsx_element(component.opening.clone(), inner);
+                        children.push(JSXElementChild::JSXElement(Box::new(element)));
+                        continue;
+                    }
+                    if !KEEP_BASIC_HTML_NODES.contains(&name.as_str()) {
+                        emit_warning(
+                            &format!("<{}> in \"{}\" has no mapping", name, message),
+                            span,
+                        );
+                    }
+                    // e.g) <0>...</0> without components keeps its children
+                    if !name.starts_with(Ident::is_valid_start) {
+                        children.extend(inner);
+                        continue;
+                    }
+                    let opening = JSXOpeningElement {
                         span: DUMMY_SP,
-                        value: JsWord::from(message),
-                        raw: None,
-                    }))));
+                        name: JSXElementName::Ident(Ident::new(name.into(), DUMMY_SP)),
+                        attrs: vec![],
+                        self_closing: false,
+                        type_args: None,
+                    };
+                    children.push(JSXElementChild::JSXElement(Box::new(jsx_element(
+                        opening, inner,
+                    ))));
                 }
-                if fragment.children.len() == 1 {
-                    if let Some(JSXElementChild::JSXText(text)) = &fragment.children.get(0) {
-                        return Ok(Box::new(Expr::Lit(Lit::Str(Str {
+            }
+        }
+        Ok(children)
+    }
+
+    /// Makes children of <Trans /> from the message of the key picking plural variants by count
+    /// e.g) <Trans i18nKey="item" count={n} /> -> ((count) => { switch(...) { case "one": return <>{count} item</>; ... } })(n)
+    /// None if the key is not found
+    fn make_trans_node(
+        &self,
+        filename: &str,
+        path: &str,
+        key: &str,
+        attrs: &TransAttrs,
+        span: Span,
+    ) -> Result<Option<Box<Expr>>, String> {
+        let plurals = match attrs.count {
+            Some(_) => self
+                .cache
+                .get_plurals(filename, path)
+                .map_err(|error| error.to_string())?,
+            None => vec![],
+        };
+        let value = match self.cache.get(filename.to_string(), path.to_string()) {
+            Ok(value) => Some(value),
+            Err(CacheError::KeyNotFound(_)) => None,
+            Err(error) => return Err(error.to_string()),
+        };
+
+        // evaluates values once e.g) values={getValues()} -> ((values) => <>{values.name}</>)(getValues())
+        let hoisted = attrs
+            .values
+            .as_deref()
+            .filter(|values| match values {
+                Expr::Ident(_) => false,
+                Expr::Object(obj) => !is_inlinable(obj),
+                _ => true,
+            })
+            .map(|values| (private_ident!("values"), values));
+        let mut trans_values = TransValues {
+            placeholders: HashMap::new(),
+            values: None,
+            keys: None,
+        };
+        if let Some(count) = &attrs.count {
+            trans_values
+                .placeholders
+                .insert("count".to_string(), count.clone());
+        }
+        match (attrs.values.as_deref(), &hoisted) {
+            (Some(Expr::Object(obj)), None) => {
+                trans_values.placeholders.extend(props_from_object(obj))
+            }
+            (Some(values), Some((param, _))) => {
+                trans_values.values = Some(Box::new(Expr::Ident(param.clone())));
+                if let Expr::Object(obj) = values {
+                    trans_values.keys = static_keys(obj);
+                }
+            }
+            (Some(values), None) => trans_values.values = Some(Box::new(values.clone())),
+            (None, _) => {}
+        }
+
+        let make_message = |message: &str| {
+            self.make_trans_message(message, key, attrs, &trans_values, span)
+                .map(Some)
+        };
+        let node = match attrs.count.as_deref().filter(|_| !plurals.is_empty()) {
+            None => match value {
+                Some(value) => make_message(&value)?,
+                None => None,
+            },
+            Some(count) => match self.literal_plural_category(&plurals, count) {
+                // e.g) <Trans i18nKey="item" count={1} /> picks a variant at compile time
+                Some(category) => match plurals
+                    .iter()
+                    .find(|(suffix, _)| *suffix == category)
+                    .or_else(|| plurals.iter().find(|(suffix, _)| *suffix == "other"))
+                    .map(|(_, message)| message.clone())
+                    .or(value)
+                {
+                    Some(message) => make_message(&message)?,
+                    None => None,
+                },
+                // e.g) <Trans i18nKey="item" count={n} /> picks a variant at runtime
+                None => {
+                    // evaluates count once for the selector and the placeholders
+                    let param = private_ident!("count");
+                    let stmt = self.make_plural_stmt(
+                        &plurals,
+                        value.as_deref(),
+                        Box::new(Expr::Ident(param.clone())),
+                        |message| {
+                            let mut trans_values = trans_values.clone();
+                            trans_values
+                                .placeholders
+                                .insert("count".to_string(), Box::new(Expr::Ident(param.clone())));
+                            self.make_trans_message(message, key, attrs, &trans_values, span)
+                        },
+                    )?;
+                    Some(Box::new(Expr::Call(self.make_iife(
+                        param,
+                        BlockStmtOrExpr::BlockStmt(BlockStmt {
                             span: DUMMY_SP,
-                            value: JsWord::from(text.value.to_string()),
-                            raw: None,
-                        }))));
-                    }
+                            stmts: vec![stmt],
+                        }),
+                        Box::new(count.clone()).into(),
+                    ))))
                 }
+            },
+        };
+
+        Ok(match (node, hoisted) {
+            (Some(node), Some((param, values))) => Some(Box::new(Expr::Call(self.make_iife(
+                param,
+                BlockStmtOrExpr::Expr(node),
+                Box::new(values.clone()).into(),
+            )))),
+            (node, _) => node,
+        })
+    }
+
+    /// Makes a node of a message of <Trans /> replacing its components and placeholders
+    /// e.g) "Hello <bold>{{name}}</bold>" -> <>Hello <strong>{name}</strong></>
+    fn make_trans_message(
+        &self,
+        message: &str,
+        key: &str,
+        attrs: &TransAttrs,
+        values: &TransValues,
+        span: Span,
+    ) -> Result<Box<Expr>, String> {
+        self.make_component_interporation_node(message, &attrs.components, Some(values), span)
+            .map_err(|error| {
+                format!(
+                    "failed to make node from {} for {}. {}",
+                    message, key, error
+                )
+            })
+    }
+
+    /// Returns namespaces of a scope, or the default namespace for keys out of useTranslation
+    /// e.g) i18next.t("foo"), useTranslation() -> ["translation"]
+    fn namespaces_or_default<'a>(&'a self, namespaces: &'a [String]) -> &'a [String] {
+        if namespaces.is_empty() {
+            std::slice::from_ref(&self.default_ns)
+        } else {
+            namespaces
+        }
+    }
+
+    /// Returns a namespace and a path of the key loading the namespace out of the scope
+    /// e.g) i18next.t("common:foo") -> ("common", "foo")
+    fn json_path(&mut self, key: &Str, scope: &Scope) -> (String, String) {
+        let (filename, path) = json_path_from_key(
+            key.value.to_string(),
+            self.namespaces_or_default(&scope.namespaces),
+            scope.key_prefix.as_deref(),
+            self.ns_separator.as_deref(),
+            self.key_separator.as_deref(),
+        );
+        if !filename.is_empty() && !scope.namespaces.contains(&filename) {
+            if let Err(error) = self.cache.add_file(filename.clone()) {
+                emit_error(&error.to_string(), key.span);
+            }
+        }
+        (filename, path)
+    }
+
+    /// Makes a node for t("foo") or t("foo", "default") e.g) "bar", <>bar <b>baz</b></>
+    fn make_message_node(
+        &mut self,
+        key: &Str,
+        scope: &Scope,
+        default: Option<Box<Expr>>,
+    ) -> Option<Box<Expr>> {
+        let (filename, path) = self.json_path(key, scope);
+        if let Some(default) = &default {
+            self.check_default(&filename, &path, default, key.span);
+        }
+        match self.cache.get(filename.clone(), path.clone()) {
+            Ok(value) => {
+                let empty = HashMap::new();
+                let map = self.component_maps.last().unwrap_or(&empty);
+                let node = self.make_component_interporation_node(&value, map, None, key.span);
+                if let Err(error) = &node {
+                    emit_error(
+                        &format!(
+                            "failed to make node from {} for {}. {}",
+                            value, key.value, error
+                        ),
+                        key.span,
+                    );
+                }
+                node.ok()
+            }
+            Err(CacheError::KeyNotFound(_)) => match default {
+                Some(default) => Some(default),
+                None => self.missing_key(&filename, &path, &key.value, None, key.span),
+            },
+            Err(error) => {
+                emit_error(&error.to_string(), key.span);
+                None
+            }
+        }
+    }
+
+    /// Makes a node for t("foo", { bar: "a" }) resolving plural and context variants
+    /// e.g) foo_one, foo_other, foo_male, foo_male_one
+    fn make_translation_node(
+        &mut self,
+        key: &Str,
+        scope: &Scope,
+        default: Option<Box<Expr>>,
+        mut options: ExprOrSpread,
+    ) -> Option<Box<Expr>> {
+        let (filename, path) = self.json_path(key, scope);
+        // e.g) t("foo", { defaultValue: "Foo" }) is not an interpolation variable
+        let default = default.or_else(|| default_value_from_options(&options.expr));
+        if let Expr::Object(obj) = &mut *options.expr {
+            remove_prop(obj, "defaultValue");
+        }
+        if let Some(default) = &default {
+            self.check_default(&filename, &path, default, key.span);
+        }
+        let count = count_from_options(&options.expr);
+        let stmt = match context_from_options(&options.expr).map(|context| *context) {
+            // e.g) t("foo", { context: "male" }) picks a variant at compile time
+            Some(Expr::Lit(Lit::Str(context))) => {
+                let context_path = format!("{}_{}", path, context.value);
+                let path = if self.cache.has_key(&filename, &context_path) {
+                    context_path
+                } else {
+                    path.clone()
+                };
+                self.make_message_stmt(&filename, &path, count.as_deref())
             }
-            Ok(node.clone())
+            // e.g) t("foo", { context: gender }) picks a variant at runtime
+            Some(_) => self.make_context_stmt(&filename, &path, &key.value, count.as_deref()),
+            None => self.make_message_stmt(&filename, &path, count.as_deref()),
+        };
+        let stmt = match (stmt, default) {
+            // e.g) t("foo", { defaultValue: "Hello {{name}}", name }) interpolates the default
+            (Ok(None), Some(default)) => match *default {
+                Expr::Lit(Lit::Str(default)) => self.make_return_stmt(&default.value).map(Some),
+                default => return Some(Box::new(default)),
+            },
+            (stmt, _) => stmt,
+        };
+
+        if let (Ok(Some(stmt)), Expr::Object(obj)) = (&stmt, &*options.expr) {
+            if options.spread.is_none() {
+                self.check_variables(&filename, &path, stmt, obj, key.span);
+            }
+        }
+
+        match stmt {
+            Ok(Some(Stmt::Return(ReturnStmt {
+                span: _,
+                arg: Some(arg),
+            }))) => Some(
+                inline_values(&arg, &options, &self.escape.to_id()).unwrap_or_else(|| {
+                    Box::new(Expr::Call(self.make_iife(
+                        Ident::new("v".into(), DUMMY_SP),
+                        BlockStmtOrExpr::Expr(arg),
+                        options,
+                    )))
+                }),
+            ),
+            Ok(Some(stmt)) => Some(Box::new(Expr::Call(self.make_iife(
+                Ident::new("v".into(), DUMMY_SP),
+                BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![stmt],
+                }),
+                options,
+            )))),
+            Ok(None) => self.missing_key(&filename, &path, &key.value, None, key.span),
+            Err(error) => {
+                emit_error(&error, key.span);
+                None
+            }
+        }
+    }
+
+    /// Reports variables missing in options or unused by the message
+    /// e.g) t("foo", { min, step }) for "{{min}} - {{max}}" misses max and doesn't use step
+    fn check_variables(
+        &self,
+        filename: &str,
+        path: &str,
+        stmt: &Stmt,
+        obj: &ObjectLit,
+        span: Span,
+    ) {
+        if self.variable_check == VariableCheck::Off {
+            return;
+        }
+        let Some(keys) = static_keys(obj) else {
+            return;
+        };
+        let names = value_names(stmt);
+        let missing: Vec<_> = names.difference(&keys).map(String::as_str).collect();
+        let unused: Vec<_> = keys
+            .difference(&names)
+            .map(String::as_str)
+            // e.g) defaultValue_other for plurals
+            .filter(|key| !OPTION_NAMES.contains(key) && !key.starts_with("defaultValue"))
+            .collect();
+        let mut messages = vec![];
+        if !missing.is_empty() {
+            messages.push(format!(
+                "missing variables for key: {}:{}: {}",
+                filename,
+                path,
+                missing.join(", ")
+            ));
+        }
+        if !unused.is_empty() {
+            messages.push(format!(
+                "unused variables for key: {}:{}: {}",
+                filename,
+                path,
+                unused.join(", ")
+            ));
+        }
+        for message in messages {
+            match self.variable_check {
+                VariableCheck::Error => emit_error(&message, span),
+                VariableCheck::Warn => emit_warning(&message, span),
+                VariableCheck::Off => {}
+            }
+        }
+    }
+
+    /// Warns if the default differs from the message in catalogs
+    fn check_default(&self, filename: &str, path: &str, default: &Expr, span: Span) {
+        let Expr::Lit(Lit::Str(default)) = default else {
+            return;
+        };
+        if let Ok(value) = self.cache.get(filename.to_string(), path.to_string()) {
+            if *default.value != value {
+                emit_warning(
+                    &format!(
+                        "default value of key: {}:{} differs from catalogs. \"{}\" != \"{}\"",
+                        filename, path, default.value, value
+                    ),
+                    span,
+                );
+            }
+        }
+    }
+
+    /// Makes a statement returning the message of the key with its plural variants
+    /// e.g) return `...`; or switch(new Intl.PluralRules("en").select(v.count)) { ... }
+    fn make_message_stmt(
+        &self,
+        filename: &str,
+        path: &str,
+        count: Option<&Expr>,
+    ) -> Result<Option<Stmt>, String> {
+        let plurals = self
+            .cache
+            .get_plurals(filename, path)
+            .map_err(|error| error.to_string())?;
+        let value = match self.cache.get(filename.to_string(), path.to_string()) {
+            Ok(value) => Some(value),
+            Err(CacheError::KeyNotFound(_)) => None,
+            Err(error) => return Err(error.to_string()),
+        };
+
+        if plurals.is_empty() || (count.is_none() && value.is_some()) {
+            return value.map(|value| self.make_return_stmt(&value)).transpose();
+        }
+
+        match count.and_then(|count| self.literal_plural_category(&plurals, count)) {
+            // e.g) t("foo", { count: 1 }) picks a variant at compile time
+            Some(category) => plurals
+                .iter()
+                .find(|(suffix, _)| *suffix == category)
+                .or_else(|| plurals.iter().find(|(suffix, _)| *suffix == "other"))
+                .map(|(_, message)| message.clone())
+                .or(value)
+                .map(|message| self.make_return_stmt(&message))
+                .transpose(),
+            // e.g) t("foo", { count }) picks a variant at runtime
+            // as does t("foo", { count: 1 }) for locales whose rules are not known e.g) hi, hr
+            None => self
+                .make_plural_stmt(
+                    &plurals,
+                    value.as_deref(),
+                    quote_expr!("v.count"),
+                    |message| self.make_template(message),
+                )
+                .map(Some),
+        }
+    }
+
+    /// Returns the plural category of a literal count e.g) 1 -> "one", 0 -> "zero" with foo_zero
+    /// None for counts known only at runtime or locales whose rules are not known
+    fn literal_plural_category(
+        &self,
+        plurals: &[(&str, String)],
+        count: &Expr,
+    ) -> Option<&'static str> {
+        let number = number_from_expr(count)?;
+        let has_zero = plurals.iter().any(|(suffix, _)| *suffix == "zero");
+        if has_zero && number.parse::<f64>() == Ok(0.0) {
+            Some("zero")
         } else {
-            Err("invalid message format detected")
+            plural_category(&self.locale, &number)
         }
     }
 
-    pub fn make_interporation_node(
+    /// e.g) switch(v.context) { case "male": return `...`; default: return `...`; }
+    fn make_context_stmt(
         &self,
-        message: String,
-        arg: ExprOrSpread,
-    ) -> Result<CallExpr, &str> {
-        let mut n = parse_code(message);
-        if let Ok(tpl) = &mut n {
-            if let Expr::Tpl(tpl) = &mut **tpl {
-                for expr in &mut tpl.exprs {
-                    if let Expr::Ident(ident) = &mut **expr {
-                        ident.sym = JsWord::from(format!("v.{}", ident.sym));
+        filename: &str,
+        path: &str,
+        key: &str,
+        count: Option<&Expr>,
+    ) -> Result<Option<Stmt>, String> {
+        let mut cases = vec![];
+        for context in self.cache.get_contexts(filename, path) {
+            let context_path = format!("{}_{}", path, context);
+            if let Some(stmt) = self.make_message_stmt(filename, &context_path, count)? {
+                cases.push(SwitchCase {
+                    span: DUMMY_SP,
+                    test: Some(Box::new(Expr::Lit(Lit::Str(context.as_str().into())))),
+                    cons: vec![stmt],
+                });
+            }
+        }
+
+        // falls back to the base key, or to the key itself as i18next does at runtime
+        let fallback = match self.make_message_stmt(filename, path, count)? {
+            Some(stmt) => stmt,
+            None if !cases.is_empty() => Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(Box::new(Expr::Lit(Lit::Str(key.into())))),
+            }),
+            None => return Ok(None),
+        };
+        if cases.is_empty() {
+            return Ok(Some(fallback));
+        }
+        cases.push(SwitchCase {
+            span: DUMMY_SP,
+            test: None,
+            cons: vec![fallback],
+        });
+
+        Ok(Some(Stmt::Switch(SwitchStmt {
+            span: DUMMY_SP,
+            discriminant: quote_expr!("v.context"),
+            cases,
+        })))
+    }
+
+    /// e.g) switch(new Intl.PluralRules("en").select(v.count)) { case "one": return `...`; default: return `...`; }
+    fn make_plural_stmt(
+        &self,
+        plurals: &[(&str, String)],
+        fallback: Option<&str>,
+        count: Box<Expr>,
+        make_message: impl Fn(&str) -> Result<Box<Expr>, String>,
+    ) -> Result<Stmt, String> {
+        let locale = Expr::Lit(Lit::Str(self.locale.as_str().into()));
+        let mut selector = quote_expr!(
+            "new Intl.PluralRules($locale).select($count)",
+            locale: Expr = locale,
+            count: Expr = *count.clone()
+        );
+        if plurals.iter().any(|(suffix, _)| *suffix == "zero") {
+            // i18next always prefers key_zero for count === 0
+            selector = quote_expr!(
+                "$count === 0 ? \"zero\" : $selector",
+                count: Expr = *count,
+                selector: Expr = *selector
+            );
+        }
+        let make_return_stmt = |message: &str| {
+            Ok::<_, String>(Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(make_message(message)?),
+            }))
+        };
+
+        let mut cases = vec![];
+        for (suffix, message) in plurals.iter().filter(|(suffix, _)| *suffix != "other") {
+            cases.push(SwitchCase {
+                span: DUMMY_SP,
+                test: Some(Box::new(Expr::Lit(Lit::Str((*suffix).into())))),
+                cons: vec![make_return_stmt(message)?],
+            });
+        }
+        let other = plurals
+            .iter()
+            .find(|(suffix, _)| *suffix == "other")
+            .map(|(_, message)| message.as_str())
+            .or(fallback);
+        if let Some(other) = other {
+            cases.push(SwitchCase {
+                span: DUMMY_SP,
+                test: None,
+                cons: vec![make_return_stmt(other)?],
+            });
+        }
+
+        Ok(Stmt::Switch(SwitchStmt {
+            span: DUMMY_SP,
+            discriminant: selector,
+            cases,
+        }))
+    }
+
+    /// e.g) return `value: ${v.max}`;
+    fn make_return_stmt(&self, message: &str) -> Result<Stmt, String> {
+        Ok(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(self.make_template(message)?),
+        }))
+    }
+
+    /// Makes a template literal from a message e.g) "value: {{max}}" -> `value: ${escape(v.max)}`
+    /// Formatters are compiled into Intl e.g) "{{price, currency(USD)}}" -> `${new Intl.NumberFormat(...).format(v.price)}`
+    fn make_template(&self, message: &str) -> Result<Box<Expr>, String> {
+        // texts followed by nestings pluralized at runtime e.g) $t(girl, {"count": "{{girls}}"})
+        let mut parts = vec![];
+        let mut last = 0;
+        for nesting in find_nestings(message) {
+            if let Some(expr) = self.make_nesting_expr(&nesting)? {
+                parts.push((&message[last..nesting.range.start], Some(expr)));
+                last = nesting.range.end;
+            }
+        }
+        parts.push((&message[last..], None));
+
+        let mut quasis = vec![String::new()];
+        let mut exprs = vec![];
+        for (text, nesting) in parts {
+            for segment in tokenize(text) {
+                match segment {
+                    Segment::Text(text) => quasis.last_mut().unwrap().push_str(&text),
+                    Segment::Placeholder { name, formats, raw } => {
+                        let mut expr = self.make_placeholder_expr(&name);
+                        let mut is_safe = false;
+                        for format in formats {
+                            let format = Format::parse(&format)?;
+                            expr = format.to_expr(*expr, &self.locale);
+                            is_safe = format.is_html_safe();
+                        }
+                        if self.escape_value && !raw && !is_safe {
+                            expr = quote_expr!("$escape($value)", escape = self.escape.clone(), value: Expr = *expr);
+                        }
+                        exprs.push(expr);
+                        quasis.push(String::new());
                     }
                 }
             }
-            Ok(CallExpr {
+            if let Some(expr) = nesting {
+                exprs.push(expr);
+                quasis.push(String::new());
+            }
+        }
+        let len = quasis.len();
+        Ok(Box::new(Expr::Tpl(Tpl {
+            span: DUMMY_SP,
+            exprs,
+            quasis: quasis
+                .into_iter()
+                .enumerate()
+                .map(|(i, text)| TplElement {
+                    span: DUMMY_SP,
+                    tail: i + 1 == len,
+                    raw: escape_template(&text).into(),
+                    cooked: Some(text.into()),
+                })
+                .collect(),
+        })))
+    }
+
+    /// Makes a nesting pluralized by a value at runtime
+    /// e.g) $t(girls, {"count": "{{girls}}"}) -> (() => { switch(...) { case "one": return `...`; } })()
+    fn make_nesting_expr(&self, nesting: &Nesting) -> Result<Option<Box<Expr>>, String> {
+        let Some(options) = nesting
+            .options
+            .and_then(|options| parse_options(options).ok())
+        else {
+            return Ok(None);
+        };
+        let Some(count) = options.get("count").and_then(option_placeholder) else {
+            return Ok(None);
+        };
+        let (filename, key) = self
+            .ns_separator
+            .as_deref()
+            .and_then(|separator| nesting.key.split_once(separator))
+            .unwrap_or((&self.default_ns, nesting.key));
+        let plurals = self
+            .cache
+            .get_plurals(filename, key)
+            .map_err(|error| format!("failed to resolve $t({}). {}", nesting.key, error))?;
+        let plurals = plurals
+            .into_iter()
+            .map(|(suffix, message)| (suffix, interpolate_options(&message, &options)))
+            .collect::<Vec<_>>();
+        let fallback = self
+            .cache
+            .get(filename.to_string(), key.to_string())
+            .ok()
+            .map(|message| interpolate_options(&message, &options));
+        let stmt = self.make_plural_stmt(
+            &plurals,
+            fallback.as_deref(),
+            self.make_placeholder_expr(count),
+            |message| self.make_template(message),
+        )?;
+
+        Ok(Some(Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Paren(ParenExpr {
                 span: DUMMY_SP,
-                callee: Callee::Expr(Box::new(Expr::Arrow(ArrowExpr {
+                expr: Box::new(Expr::Arrow(ArrowExpr {
                     span: DUMMY_SP,
-                    params: vec![Pat::Ident(BindingIdent {
-                        id: Ident {
-                            span: DUMMY_SP,
-                            sym: JsWord::from("v"),
-                            optional: false,
-                        },
-                        type_ann: None,
-                    })],
-                    body: Box::new(BlockStmtOrExpr::Expr(tpl.clone())),
+                    params: vec![],
+                    body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![stmt],
+                    })),
                     type_params: None,
                     return_type: None,
                     is_async: false,
                     is_generator: false,
-                }))),
-                args: vec![arg],
-                type_args: None,
-            })
-        } else {
-            Err("todo")
+                })),
+            }))),
+            args: vec![],
+            type_args: None,
+        }))))
+    }
+
+    /// Splits the path of a placeholder by the key separator as i18next does
+    /// e.g) user.name -> ["user", "name"]
+    fn placeholder_path<'a>(&self, name: &'a str) -> Vec<&'a str> {
+        match self.key_separator.as_deref() {
+            Some(separator) if !separator.is_empty() => name
+                .split(separator)
+                .map(|segment| segment.trim())
+                .collect(),
+            _ => vec![name.trim()],
         }
     }
+
+    /// Follows the path of a placeholder split by the key separator as i18next does
+    /// e.g) name -> v.name, user.name -> v.user.name, first-name -> v["first-name"]
+    fn make_placeholder_expr(&self, name: &str) -> Box<Expr> {
+        let v = Box::new(Expr::Ident(Ident::new("v".into(), DUMMY_SP)));
+        member_path(v, &self.placeholder_path(name))
+    }
+
+    /// Makes the helper escaping values as i18next does
+    /// e.g) const escape = (value) => String(value).replace(/[&<>"'\/]/g, ...);
+    fn escape_helper(&self) -> Stmt {
+        quote!(
+            "const $escape = (value) => String(value).replace(/[&<>\"'\\/]/g, (c) => ({
+                '&': '&amp;', '<': '&lt;', '>': '&gt;', '\"': '&quot;', \"'\": '&#39;', '/': '&#x2F;'
+            })[c]);" as Stmt,
+            escape = self.escape.clone()
+        )
+    }
+
+    /// e.g) ((v)=>body)(arg)
+    fn make_iife(&self, param: Ident, body: BlockStmtOrExpr, arg: ExprOrSpread) -> CallExpr {
+        CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Arrow(ArrowExpr {
+                span: DUMMY_SP,
+                params: vec![Pat::Ident(BindingIdent {
+                    id: param,
+                    type_ann: None,
+                })],
+                body: Box::new(body),
+                type_params: None,
+                return_type: None,
+                is_async: false,
+                is_generator: false,
+            }))),
+            args: vec![arg],
+            type_args: None,
+        }
+    }
+}
+
+/// Returns true for a directive e.g) "use client"
+fn is_directive(stmt: &Stmt) -> bool {
+    matches!(stmt, Stmt::Expr(expr) if matches!(&*expr.expr, Expr::Lit(Lit::Str(_))))
+}
+
+/// Returns true for an argument passed as options of t()
+/// e.g) { bar: "a" }, options, getOptions() but not "default", `default`, ...args
+fn is_options(arg: &ExprOrSpread) -> bool {
+    arg.spread.is_none() && !matches!(&*arg.expr, Expr::Lit(_) | Expr::Tpl(_))
+}
+
+/// Attributes of <Trans /> for its message e.g) <Trans components={{ bold: <b /> }} values={{ name }} count={n} />
+struct TransAttrs {
+    components: HashMap<String, JSXElement>,
+    values: Option<Box<Expr>>,
+    count: Option<Box<Expr>>,
+}
+
+/// Expressions of placeholders of <Trans /> passed via `values` and `count`
+#[derive(Clone)]
+struct TransValues {
+    placeholders: HashMap<String, Box<Expr>>,
+    /// values read by members e.g) values={values} -> {values.name}
+    values: Option<Box<Expr>>,
+    /// keys of the values if known e.g) values={{ name: getName() }} -> ["name"]
+    keys: Option<BTreeSet<String>>,
+}
+
+impl TransValues {
+    /// Resolves the path of a placeholder e.g) user.name -> user.name, values.user.name
+    fn resolve(&self, path: &[&str]) -> Option<Box<Expr>> {
+        let (root, props) = path.split_first()?;
+        let value = match (self.placeholders.get(*root), &self.values) {
+            (Some(value), _) => value.clone(),
+            (None, Some(values)) if self.keys.as_ref().is_none_or(|keys| keys.contains(*root)) => {
+                member_path(values.clone(), &[root])
+            }
+            _ => return None,
+        };
+        Some(member_path(value, props))
+    }
+}
+
+/// Follows a path of members e.g) (v, ["user", "first-name"]) -> v.user["first-name"]
+fn member_path(obj: Box<Expr>, path: &[&str]) -> Box<Expr> {
+    let mut expr = obj;
+    for segment in path {
+        let mut chars = segment.chars();
+        let is_ident =
+            chars.next().is_some_and(Ident::is_valid_start) && chars.all(Ident::is_valid_continue);
+        let prop = if is_ident {
+            MemberProp::Ident(Ident::new((*segment).into(), DUMMY_SP))
+        } else {
+            MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: Box::new(Expr::Lit(Lit::Str((*segment).into()))),
+            })
+        };
+        expr = Box::new(Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: expr,
+            prop,
+        }));
+    }
+    expr
+}
+
+/// Makes a child of a text of a message, quoting texts which are not plain in JSX
+/// e.g) "hello" -> hello, "1 < 2" -> {"1 < 2"}
+fn jsx_text(text: &str) -> JSXElementChild {
+    if text.contains(['{', '}', '<', '>']) {
+        return JSXElementChild::JSXExprContainer(JSXExprContainer {
+            span: DUMMY_SP,
+            expr: JSXExpr::Expr(Box::new(Expr::Lit(Lit::Str(text.into())))),
+        });
+    }
+    JSXElementChild::JSXText(JSXText {
+        span: DUMMY_SP,
+        value: text.into(),
+        raw: text.into(),
+    })
+}
+
+/// Makes an element of a tag of a message e.g) <strong>...</strong>, <br />
+fn jsx_element(opening: JSXOpeningElement, children: Vec<JSXElementChild>) -> JSXElement {
+    let has_children = !children.is_empty();
+    JSXElement {
+        span: DUMMY_SP,
+        closing: has_children.then(|| JSXClosingElement {
+            span: DUMMY_SP,
+            name: opening.name.clone(),
+        }),
+        opening: JSXOpeningElement {
+            span: DUMMY_SP,
+            self_closing: !has_children,
+            ..opening
+        },
+        children,
+    }
 }
 
 #[plugin_transform]
@@ -489,7 +2047,7 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
     )
     .expect("invalid config");
 
-    program.fold_with(&mut as_folder(TransformVisitor::new(config.base_dir)))
+    program.fold_with(&mut as_folder(TransformVisitor::new(config)))
 }
 
 #[cfg(test)]
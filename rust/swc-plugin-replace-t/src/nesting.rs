@@ -0,0 +1,190 @@
+use std::ops::Range;
+
+use serde_json::{Map, Value};
+
+/// A nested translation in a message e.g) $t(common:appName, {"count": 1})
+/// https://www.i18next.com/translation-function/nesting
+#[derive(Debug, PartialEq)]
+pub struct Nesting<'a> {
+    pub range: Range<usize>,
+    pub key: &'a str,
+    pub options: Option<&'a str>,
+}
+
+/// Finds nested translations in a message
+pub fn find_nestings(message: &str) -> Vec<Nesting<'_>> {
+    let mut nestings = vec![];
+    let mut offset = 0;
+    while let Some(start) = message[offset..].find("$t(").map(|i| i + offset) {
+        let body = start + 3;
+        let mut depth = 0;
+        let mut quote = None;
+        let mut separator = None;
+        let mut end = None;
+        for (i, c) in message[body..].char_indices() {
+            let i = i + body;
+            match (quote, c) {
+                (Some(_), '\\') => {}
+                (Some(q), c) if c == q && !message[..i].ends_with('\\') => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(' | '{' | '[') => depth += 1,
+                (None, ')') if depth == 0 => {
+                    end = Some(i);
+                    break;
+                }
+                (None, ')' | '}' | ']') => depth -= 1,
+                (None, ',') if depth == 0 && separator.is_none() => separator = Some(i),
+                _ => {}
+            }
+        }
+        let Some(end) = end else {
+            break;
+        };
+        let (key, options) = match separator {
+            Some(separator) => (
+                &message[body..separator],
+                Some(message[separator + 1..end].trim()),
+            ),
+            None => (&message[body..end], None),
+        };
+        nestings.push(Nesting {
+            range: start..end + 1,
+            key: key.trim(),
+            options,
+        });
+        offset = end + 1;
+    }
+    nestings
+}
+
+/// Parses options of a nested translation the way i18next does
+/// e.g) {'count': {{girls}} } -> {"count": "{{girls}}"}
+pub fn parse_options(options: &str) -> Result<Map<String, Value>, serde_json::Error> {
+    // i18next swaps single quotes when they are balanced or double quotes are not
+    let singles = options.matches('\'').count();
+    let doubles = options.matches('"').count();
+    let options = if (singles.is_multiple_of(2) && doubles == 0) || !doubles.is_multiple_of(2) {
+        options.replace('\'', "\"")
+    } else {
+        options.to_string()
+    };
+
+    // placeholders of the outer message are kept as strings to be resolved later
+    let mut quoted = String::with_capacity(options.len());
+    let mut in_string = false;
+    let mut rest = options.as_str();
+    while let Some(c) = rest.chars().next() {
+        if !in_string && rest.starts_with("{{") {
+            if let Some(end) = rest.find("}}") {
+                quoted.push('"');
+                quoted.push_str(&rest[..end + 2]);
+                quoted.push('"');
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        match c {
+            '"' => in_string = !in_string,
+            '\\' if in_string => {
+                quoted.push(c);
+                rest = &rest[1..];
+                let Some(escaped) = rest.chars().next() else {
+                    break;
+                };
+                quoted.push(escaped);
+                rest = &rest[escaped.len_utf8()..];
+                continue;
+            }
+            _ => {}
+        }
+        quoted.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    serde_json::from_str(&quoted)
+}
+
+/// Interpolates options into a nested translation e.g) {{name}} with {"name": "foo"}
+pub fn interpolate_options(message: &str, options: &Map<String, Value>) -> String {
+    options
+        .iter()
+        .fold(message.to_string(), |message, (name, option)| {
+            let option = match option {
+                Value::String(option) => option.to_string(),
+                option => option.to_string(),
+            };
+            message.replace(&format!("{{{{{}}}}}", name), &option)
+        })
+}
+
+/// Returns a placeholder name if the option is a placeholder e.g) "{{girls}}" -> girls
+pub fn option_placeholder(option: &Value) -> Option<&str> {
+    option
+        .as_str()?
+        .strip_prefix("{{")?
+        .strip_suffix("}}")
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_find_nestings() {
+        assert_eq!(
+            find_nestings("Welcome to $t(appName)!"),
+            vec![Nesting {
+                range: 11..22,
+                key: "appName",
+                options: None,
+            }]
+        );
+        assert_eq!(
+            find_nestings(r#"$t(common:item, {"count": 1, "name": "a)b"}) and $t(x.y)"#),
+            vec![
+                Nesting {
+                    range: 0..44,
+                    key: "common:item",
+                    options: Some(r#"{"count": 1, "name": "a)b"}"#),
+                },
+                Nesting {
+                    range: 49..56,
+                    key: "x.y",
+                    options: None,
+                }
+            ]
+        );
+        assert!(find_nestings("no nesting $t(unclosed").is_empty());
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let parse = |options| Value::Object(parse_options(options).unwrap());
+        assert_eq!(parse(r#"{"count": 2}"#), json!({"count": 2}));
+        assert_eq!(parse("{'count': 2}"), json!({"count": 2}));
+        assert_eq!(
+            parse(r#"{"count": {{girls}} }"#),
+            json!({"count": "{{girls}}"})
+        );
+        assert_eq!(
+            parse(r#"{"name": "{{user}}", "note": "it's"}"#),
+            json!({"name": "{{user}}", "note": "it's"})
+        );
+        assert!(parse_options("{count: 2}").is_err());
+    }
+
+    #[test]
+    fn test_interpolate_options() {
+        let options = parse_options(r#"{"count": {{girls}}, "name": "A"}"#).unwrap();
+        assert_eq!(
+            interpolate_options("{{count}} girls with {{name}}", &options),
+            "{{girls}} girls with A"
+        );
+        assert_eq!(option_placeholder(&options["count"]), Some("girls"));
+        assert_eq!(option_placeholder(&options["name"]), None);
+    }
+}
@@ -0,0 +1,255 @@
+/// Plural suffixes of i18next e.g) key_one, key_other
+/// https://www.i18next.com/translation-function/plurals
+pub const PLURAL_SUFFIXES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// Operands of CLDR plural rules
+/// https://unicode.org/reports/tr35/tr35-numbers.html#Operands
+struct Operands {
+    n: f64,
+    i: u64,
+    v: usize,
+}
+
+impl Operands {
+    fn parse(number: &str) -> Option<Self> {
+        let number = number.trim().trim_start_matches('-');
+        let n = number.parse::<f64>().ok()?;
+        let (i, f) = number.split_once('.').unwrap_or((number, ""));
+        Some(Self {
+            n,
+            i: i.parse().ok()?,
+            v: f.len(),
+        })
+    }
+}
+
+/// Returns the CLDR plural category of a number for the locale e.g) ("en", "1") -> "one"
+/// The number is given as a string to keep visible fraction digits e.g) "1.0" -> "other"
+/// None for locales whose rules are not known, which are left for Intl.PluralRules at runtime
+pub fn plural_category(locale: &str, number: &str) -> Option<&'static str> {
+    let Some(Operands { n, i, v }) = Operands::parse(number) else {
+        return Some("other");
+    };
+    // e.g) pt_PT, pt-Latn-PT -> ("pt", Some("PT"))
+    let mut subtags = locale.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default().to_lowercase();
+    let region = subtags
+        .find(|subtag| {
+            subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic())
+                || subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit())
+        })
+        .map(|region| region.to_uppercase());
+
+    let category = match language.as_str() {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" | "lo" | "my" => "other",
+        "fr" => {
+            if i <= 1 {
+                "one"
+            } else if v == 0 && i % 1_000_000 == 0 {
+                "many"
+            } else {
+                "other"
+            }
+        }
+        "pt" => {
+            let one = if region.as_deref() == Some("PT") {
+                i == 1 && v == 0
+            } else {
+                i <= 1
+            };
+            if one {
+                "one"
+            } else if v == 0 && i != 0 && i % 1_000_000 == 0 {
+                "many"
+            } else {
+                "other"
+            }
+        }
+        "es" | "it" | "ca" => {
+            let one = if language == "es" {
+                n == 1.0
+            } else {
+                i == 1 && v == 0
+            };
+            if one {
+                "one"
+            } else if v == 0 && i != 0 && i % 1_000_000 == 0 {
+                "many"
+            } else {
+                "other"
+            }
+        }
+        "ru" | "uk" | "be" => {
+            if v != 0 {
+                "other"
+            } else if i % 10 == 1 && i % 100 != 11 {
+                "one"
+            } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "pl" => {
+            if v != 0 {
+                "other"
+            } else if i == 1 {
+                "one"
+            } else if (2..=4).contains(&(i % 10)) && !(12..=14).contains(&(i % 100)) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "cs" | "sk" => {
+            if v != 0 {
+                "many"
+            } else if i == 1 {
+                "one"
+            } else if (2..=4).contains(&i) {
+                "few"
+            } else {
+                "other"
+            }
+        }
+        "ar" => {
+            if n == 0.0 {
+                "zero"
+            } else if n == 1.0 {
+                "one"
+            } else if n == 2.0 {
+                "two"
+            } else if v == 0 && (3..=10).contains(&(i % 100)) {
+                "few"
+            } else if v == 0 && (11..=99).contains(&(i % 100)) {
+                "many"
+            } else {
+                "other"
+            }
+        }
+        "he" => {
+            if (i == 1 && v == 0) || (i == 0 && v != 0) {
+                "one"
+            } else if i == 2 && v == 0 {
+                "two"
+            } else {
+                "other"
+            }
+        }
+        "en" | "de" | "nl" | "sv" | "fi" | "et" | "gl" | "fy" | "sw" | "ur" => {
+            if i == 1 && v == 0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "nb" | "no" | "nn" | "el" | "hu" | "tr" | "bg" | "az" | "ka" | "kk" | "sq" => {
+            if n == 1.0 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        _ => return None,
+    };
+    Some(category)
+}
+
+/// Returns plural categories of integers for the locale in the order of gettext plural forms
+/// e.g) "en" -> ["one", "other"], "ru" -> ["one", "few", "many"], "fr" -> ["one", "many", "other"]
+pub fn plural_categories(locale: &str) -> Option<Vec<&'static str>> {
+    sample_categories(locale, true)
+}
+
+/// Samples categories of integers e.g) "many" of fr, es, it, pt and ca starts at 1e6
+fn sample_categories(locale: &str, compact: bool) -> Option<Vec<&'static str>> {
+    let compact = compact.then_some(1_000_000).into_iter();
+    let categories = (0..=1000)
+        .chain(compact)
+        .map(|number| plural_category(locale, &number.to_string()))
+        .collect::<Option<Vec<_>>>()?;
+    Some(
+        PLURAL_SUFFIXES
+            .into_iter()
+            .filter(|suffix| categories.contains(suffix))
+            .collect(),
+    )
+}
+
+/// Returns plural categories of gettext plural forms from nplurals of the Plural-Forms header
+/// e.g) ("fr", Some(2)) -> ["one", "other"], ("fr", Some(3)) -> ["one", "many", "other"]
+pub fn plural_forms(locale: &str, nplurals: Option<usize>) -> Option<Vec<&'static str>> {
+    let Some(nplurals) = nplurals else {
+        return plural_categories(locale);
+    };
+    // older catalogs omit "many" of millions e.g) nplurals=2 for fr
+    [true, false]
+        .into_iter()
+        .filter_map(|compact| sample_categories(locale, compact))
+        .find(|categories| categories.len() == nplurals)
+        .or_else(|| match nplurals {
+            // e.g) nplurals=2; plural=(n != 1); is the default of gettext
+            1 => Some(vec!["other"]),
+            2 => Some(vec!["one", "other"]),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural_category() {
+        assert_eq!(plural_category("en", "1"), Some("one"));
+        assert_eq!(plural_category("en", "1.0"), Some("other"));
+        assert_eq!(plural_category("en-US", "0"), Some("other"));
+        assert_eq!(plural_category("ja", "1"), Some("other"));
+        assert_eq!(plural_category("fr", "0"), Some("one"));
+        assert_eq!(plural_category("fr", "1000000"), Some("many"));
+        assert_eq!(plural_category("ru", "21"), Some("one"));
+        assert_eq!(plural_category("ru", "22"), Some("few"));
+        assert_eq!(plural_category("ru", "11"), Some("many"));
+        assert_eq!(plural_category("pl", "5"), Some("many"));
+        assert_eq!(plural_category("cs", "3"), Some("few"));
+        assert_eq!(plural_category("ar", "0"), Some("zero"));
+        assert_eq!(plural_category("ar", "102"), Some("other"));
+        assert_eq!(plural_category("ar", "111"), Some("many"));
+        assert_eq!(plural_category("pt", "0"), Some("one"));
+        assert_eq!(plural_category("pt_PT", "0"), Some("other"));
+        assert_eq!(plural_category("pt-Latn-PT", "0"), Some("other"));
+        assert_eq!(plural_category("pt-BR", "0"), Some("one"));
+        assert_eq!(plural_category("hi", "0"), None);
+        assert_eq!(plural_category("hr", "2"), None);
+    }
+
+    #[test]
+    fn test_plural_categories() {
+        assert_eq!(plural_categories("en"), Some(vec!["one", "other"]));
+        assert_eq!(plural_categories("ja"), Some(vec!["other"]));
+        assert_eq!(plural_categories("fr"), Some(vec!["one", "many", "other"]));
+        assert_eq!(plural_categories("ru"), Some(vec!["one", "few", "many"]));
+        assert_eq!(
+            plural_categories("ar"),
+            Some(vec!["zero", "one", "two", "few", "many", "other"])
+        );
+        assert_eq!(plural_categories("hi"), None);
+    }
+
+    #[test]
+    fn test_plural_forms() {
+        assert_eq!(plural_forms("fr", None), Some(vec!["one", "many", "other"]));
+        assert_eq!(
+            plural_forms("fr", Some(3)),
+            Some(vec!["one", "many", "other"])
+        );
+        assert_eq!(plural_forms("fr", Some(2)), Some(vec!["one", "other"]));
+        assert_eq!(
+            plural_forms("ru", Some(3)),
+            Some(vec!["one", "few", "many"])
+        );
+        assert_eq!(plural_forms("hi", Some(2)), Some(vec!["one", "other"]));
+        assert_eq!(plural_forms("hr", Some(3)), None);
+        assert_eq!(plural_forms("hi", None), None);
+    }
+}
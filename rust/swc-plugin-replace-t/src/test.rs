@@ -1,16 +1,30 @@
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 use swc_core::{
-    common::{chain, Mark},
+    common::{
+        chain,
+        errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+        Mark,
+    },
     ecma::{
-        transforms::testing::{test, Tester},
+        transforms::{
+            base::resolver,
+            testing::{test, test_transform, Tester},
+        },
         visit::{as_folder, Fold},
     },
 };
 use swc_ecma_parser::{Syntax, TsConfig};
 use swc_ecma_transforms_react::react;
 
-use crate::TransformVisitor;
+use crate::{
+    config::{Config, MissingKey, VariableCheck},
+    TransformVisitor,
+};
 
 fn syntax(tsx: bool) -> Syntax {
     Syntax::Typescript(TsConfig {
@@ -19,9 +33,52 @@ fn syntax(tsx: bool) -> Syntax {
     })
 }
 
-fn transformer(t: &Tester) -> impl Fold {
+/// Returns the absolute path of a fixture directory e.g) fixture/json
+fn fixture_dir(name: &str) -> String {
     let current_dir = std::env::current_dir().unwrap();
-    let path = current_dir.join(Path::new("fixture/json"));
+    let path = current_dir.join(Path::new("fixture")).join(name);
+    path.to_str().unwrap().to_string()
+}
+
+fn config() -> Config {
+    Config {
+        base_dir: fixture_dir("json"),
+        ..Default::default()
+    }
+}
+
+/// Resolves bindings as swc does before plugins, then transforms
+fn visitor(config: Config) -> impl Fold {
+    chain!(
+        resolver(Mark::new(), Mark::new(), true),
+        as_folder(TransformVisitor::new(config))
+    )
+}
+
+/// Collects messages of diagnostics emitted to HANDLER
+#[derive(Clone, Default)]
+struct Diagnostics(Arc<Mutex<Vec<String>>>);
+
+impl Emitter for Diagnostics {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        self.0.lock().unwrap().push(db.message());
+    }
+}
+
+/// Transforms the input and returns the warnings emitted
+fn warnings(config: Config, input: &str) -> Vec<String> {
+    let diagnostics = Diagnostics::default();
+    let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+    Tester::run(|tester| {
+        HANDLER.set(&handler, || {
+            tester.apply_transform(visitor(config), "input.js", syntax(true), input)
+        })
+    });
+    let warnings = diagnostics.0.lock().unwrap().clone();
+    warnings
+}
+
+fn transformer(t: &Tester) -> impl Fold {
     let mark = Mark::new();
     chain!(
         react(
@@ -31,10 +88,23 @@ fn transformer(t: &Tester) -> impl Fold {
             mark,
             mark
         ),
-        as_folder(TransformVisitor::new(path.to_str().unwrap().to_string()))
+        visitor(config())
     )
 }
 
+fn locale_transformer(locale: &str, fallback_locales: &[&str]) -> impl Fold {
+    visitor(Config {
+        base_dir: fixture_dir("locales"),
+        locale: locale.to_string(),
+        fallback_locales: fallback_locales
+            .iter()
+            .map(|locale| locale.to_string())
+            .collect(),
+        path: "{baseDir}/{locale}/{ns}.json".to_string(),
+        ..Default::default()
+    })
+}
+
 test!(
     syntax(false),
     |t| transformer(t),
@@ -93,6 +163,133 @@ test!(
     "#
 );
 
+test!(
+    syntax(false),
+    |t| transformer(t),
+    interporation_escape,
+    r#"
+    export const object = () => {
+        const { t } = useTranslation('object');
+        console.log(t('escape', { name: "foo", price: 100, dir: "tmp" }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    interporation_escape_inline,
+    r#"
+    export const object = ({ name, price, dir }) => {
+        const { t } = useTranslation('object');
+        console.log(t('escape', { name, price, dir }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    interporation_inline,
+    r#"
+    export const object = ({ min, max, range, values }) => {
+        const { t } = useTranslation('object');
+        console.log(t('foo', { min, max }));
+        console.log(t('foo', { min: range.min, max: range.max }));
+        console.log(t('foo', { min: 1, max: 2.5 }));
+        console.log(t('foo', { min: getMin(), max }));
+        console.log(t('foo', { min }));
+        console.log(t('foo', { ...values, min, max }));
+        console.log(t('foo', values));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    interporation_options,
+    r#"
+    export const object = (props) => {
+        const { t } = useTranslation('object');
+        console.log(t('dotted', { user: props.user }));
+        console.log(t('dotted', props.options));
+        console.log(t('dotted', getOptions()));
+        console.log(t('dotted', 'default', getOptions()));
+        console.log(t('dotted', ...args));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        escape_value: true,
+        ..config()
+    }),
+    escape_value,
+    r#"
+    "use client";
+    import { useTranslation } from 'react-i18next';
+    import escape from 'escape-html';
+    export const object = ({ html }) => {
+        const { t } = useTranslation('object');
+        console.log(t('raw', { html }));
+        console.log(t('raw', { html: "<b>Tom & Jerry</b>" }));
+        console.log(escape(html));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        escape_value: false,
+        ..config()
+    }),
+    escape_value_disabled,
+    r#"
+    export const object = ({ html }) => {
+        const { t } = useTranslation('object');
+        console.log(t('raw', { html }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        variable_check: VariableCheck::Error,
+        ..config()
+    }),
+    variable_check,
+    r#"
+    export const object = ({ min, max, n, values }) => {
+        const { t } = useTranslation('object');
+        console.log(t('foo', { min, max, lng: 'en', defaultValue: 'Foo' }));
+        console.log(t('foo', { ...values, min }));
+        console.log(t('foo', values));
+    };
+    const plural = ({ n }) => {
+        const { t } = useTranslation('plural');
+        console.log(t('item', { count: n, defaultValue_other: '{{count}} items' }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    multiple_declarators,
+    r#"
+    import { useTranslation } from 'react-i18next';
+    export const multiple = () => {
+        const x = 1, { t } = useTranslation('noArgs');
+        console.log(x, t('a'));
+    };
+    "#
+);
+
 test!(
     syntax(false),
     |t| transformer(t),
@@ -141,3 +338,828 @@ test!(
     };
     "#
 );
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    trans_i18n_key,
+    r#"
+    const ComponentWithI18nKey = ({ name, count, values }) => {
+        const { t } = useTranslation('react');
+
+        return (
+          <div>
+            <Trans i18nKey="foo" />
+            <Trans i18nKey="transComponents" components={{ link: <Link href="foo.com" />, button: <Button /> }}>
+              default
+            </Trans>
+            <Trans i18nKey="greeting" values={{ name }} components={{ bold: <strong /> }} />
+            <Trans i18nKey="greeting" values={values} />
+            <Trans i18nKey="itemCount" count={count} />
+            <Trans i18nKey="itemCount" count={1} />
+            <Trans ns="noArgs" i18nKey="x.y" />
+            <Trans i18nKey="noArgs2:c" />
+          </div>
+        );
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    trans_message_text,
+    r#"
+    const ComponentWithTexts = ({ name }) => {
+        const { t } = useTranslation('react');
+
+        return (
+          <div>
+            <Trans i18nKey="code" />
+            <Trans i18nKey="lessThan" values={{ name }} />
+            <Trans i18nKey="backtick" values={{ name }} />
+            <Trans i18nKey="indexed" values={{ name }} components={[<b />, <i />]} />
+            {t('code')}
+            {t('lessThanText')}
+            {t('backtick')}
+          </div>
+        );
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    trans_values,
+    r#"
+    const ComponentWithValues = ({ user, getValues }) => {
+        const { t } = useTranslation('react');
+
+        return (
+          <div>
+            <Trans i18nKey="greeting" values={getValues()} />
+            <Trans i18nKey="greeting" values={{ name: user.name }} />
+            <Trans i18nKey="greeting" values={{ ...user }} />
+            <Trans i18nKey="greeting" values={{ title: user.title }} />
+            <Trans i18nKey="greeting" />
+          </div>
+        );
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    trans_dotted_values,
+    r#"
+    const ComponentWithDottedValues = ({ user, values }) => {
+        const { t } = useTranslation('object');
+
+        return (
+          <div>
+            <Trans i18nKey="dotted" values={{ user }} />
+            <Trans i18nKey="dotted" values={values} />
+            <Trans i18nKey="dotted" values={{ name: user.name }} />
+          </div>
+        );
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    trans_raw_values,
+    r#"
+    const ComponentWithRawValues = ({ html }) => {
+        const { t } = useTranslation('object');
+
+        return <Trans i18nKey="raw" values={{ html }} />;
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    plural,
+    r#"
+    export const plural = ({ n, options }) => {
+        const { t } = useTranslation('plural');
+        console.log(t('item', { count: 1 }));
+        console.log(t('item', { count: 0x1 }));
+        console.log(t('item', { count: 1_000 }));
+        console.log(t('item', { count: 2 }));
+        console.log(t('message', { count: 0 }));
+        console.log(t('item', { count: n }));
+        console.log(t('message', { count: n }));
+        console.log(t('message', options));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    context,
+    r#"
+    export const context = ({ gender, count }) => {
+        const { t } = useTranslation('context');
+        console.log(t('friend', { context: 'male' }));
+        console.log(t('friend', { context: 'female', count: 2 }));
+        console.log(t('friend', { context: 'unknown' }));
+        console.log(t('friend', { context: gender, count }));
+        console.log(t('nested.title', { context: gender }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    nesting,
+    r#"
+    export const nesting = ({ user, girls, city }) => {
+        const { t } = useTranslation('nesting');
+        console.log(t('welcome'));
+        console.log(t('cross'));
+        console.log(t('greetUser', { user }));
+        console.log(t('girls', { girls, city }));
+        console.log(t('twoGirls'));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    format,
+    r#"
+    export const format = ({ price, date, diff, names }) => {
+        const { t } = useTranslation('format');
+        console.log(t('price', { price }));
+        console.log(t('total', { count: 1, total: 10 }));
+        console.log(t('date', { date }));
+        console.log(t('ago', { diff }));
+        console.log(t('members', { names }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| locale_transformer("de", &[]),
+    locale,
+    r#"
+    export const locale = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting'));
+        console.log(t('apple', { count }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| locale_transformer("en", &[]),
+    options_lng,
+    r#"
+    export const lng = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting', { lng: 'de' }));
+        console.log(t('apple', { lng: 'de', count }));
+        console.log(t('greeting'));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(config()),
+    options_ns,
+    r#"
+    export const ns = () => {
+        const { t } = useTranslation('noArgs');
+        console.log(t('c', { ns: 'noArgs2' }));
+        console.log(t('d', { ns: ['noArgs2', 'noArgs'] }));
+        console.log(t('a'));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| locale_transformer("hi", &[]),
+    unknown_plural_rules,
+    r#"
+    export const locale = () => {
+        const { t } = useTranslation('common');
+        console.log(t('apple', { count: 0 }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        base_dir: fixture_dir("formats"),
+        path: "{baseDir}/{ns}.po".to_string(),
+        ..config()
+    }),
+    catalog_po,
+    r#"
+    export const po = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting', { name: 'John' }));
+        console.log(t('greeting', { context: 'formal', name: 'John' }));
+        console.log(t('apple', { count }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        base_dir: fixture_dir("formats"),
+        path: "{baseDir}/{ns}.yaml".to_string(),
+        ..config()
+    }),
+    catalog_yaml,
+    r#"
+    export const yaml = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting', { name: 'John' }));
+        console.log(t('nested.title'));
+        console.log(t('apple', { count }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        base_dir: fixture_dir("formats"),
+        path: "{baseDir}/{ns}.json5".to_string(),
+        ..config()
+    }),
+    catalog_json5,
+    r#"
+    export const json5 = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting', { name: 'John' }));
+        console.log(t('nested.title'));
+        console.log(t('apple', { count }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| locale_transformer("de", &["en"]),
+    fallback_locales,
+    r#"
+    export const fallbackLocales = ({ count }) => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting'));
+        console.log(t('farewell'));
+        console.log(t('apple', { count }));
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(Config {
+        missing_key: MissingKey::Default,
+        missing_key_by_namespace: HashMap::from([("noArgs2".to_string(), MissingKey::Key)]),
+        ..config()
+    }),
+    missing_key,
+    r#"
+    import { useTranslation, Trans } from "react-i18next";
+
+    export const missingKey = ({ count }) => {
+        const { t } = useTranslation('noArgs');
+        console.log(t('a'));
+        console.log(t('notExists'));
+        console.log(t('notExists', { defaultValue: 'Not exists', count }));
+        return <Trans i18nKey="notExists">Not <b>exists</b></Trans>;
+    };
+
+    export const missingKeyByNamespace = () => {
+        const { t } = useTranslation('noArgs2');
+        console.log(t('notExists'));
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(Config {
+        missing_key: MissingKey::Error,
+        missing_key_by_namespace: HashMap::from([("noArgs2".to_string(), MissingKey::Warn)]),
+        ..config()
+    }),
+    missing_key_runtime,
+    r#"
+    import { useTranslation, Trans } from "react-i18next";
+
+    export const translated = () => {
+        const { t } = useTranslation('noArgs');
+        console.log(t('a'));
+    };
+
+    export const missingKeyRuntime = ({ count }) => {
+        const { t } = useTranslation('noArgs2');
+        console.log(t('c'));
+        console.log(t('notExists', { count }));
+        return <Trans i18nKey="notExists">Not exists</Trans>;
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |t| transformer(t),
+    default_value,
+    r#"
+    export const defaultValue = ({ name }) => {
+        const { t } = useTranslation('noArgs');
+        console.log(t('a', 'test1'));
+        console.log(t('a', 'Different'));
+        console.log(t('notExists', 'Default text'));
+        console.log(t('notExists', { defaultValue: 'Hello {{name}}', name }));
+        console.log(t('notExists', 'Hi {{name}}', { name }));
+        console.log(t('x.y', { defaultValue: 'test2', name }));
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(Config {
+        ns_separator: Some("::".to_string()),
+        key_separator: None,
+        ..config()
+    }),
+    separators,
+    r#"
+    export const separators = ({ name, context }) => {
+        const { t } = useTranslation('flat');
+        console.log(t('Welcome.'));
+        console.log(t('flat::Welcome.'));
+        console.log(t('Hello, {{name}}.', { name }));
+        console.log(t('friend.', { context }));
+        console.log(t('cross'));
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(Config {
+        method_names: vec!["translate".to_string()],
+        hook_names: vec!["useI18n".to_string()],
+        component_names: vec!["Message".to_string()],
+        components_attr_name: "elements".to_string(),
+        package_names: vec!["@acme/i18n".to_string()],
+        ..config()
+    }),
+    identifiers,
+    r#"
+    import { useI18n as useT, Message as Msg } from "@acme/i18n";
+    import { useTranslation } from "react-i18next";
+
+    export const identifiers = ({ name }) => {
+        const { translate } = useT('react');
+        console.log(translate('foo'));
+        return <Msg i18nKey="greeting" values={{ name }} elements={{ bold: <b /> }} />;
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(config()),
+    bindings,
+    r#"
+    const t = (key) => key;
+
+    export const renamed = () => {
+        const { t: translate } = useTranslation('noArgs');
+        console.log(translate('a'));
+        console.log(t('a'));
+        useEffect(() => {}, [translate, t]);
+    };
+
+    export const array = () => {
+        const [t] = useTranslation('noArgs');
+        console.log(t('b'));
+        const shadowed = (t) => t('a');
+    };
+
+    export const unrelated = () => {
+        console.log(t('a'));
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    scopes,
+    r#"
+    export const First = () => {
+        const { t } = useTranslation('noArgs');
+        const useInner = () => {
+            const { t: inner } = useTranslation('noArgs2');
+            return [t('a'), inner('c')];
+        };
+        return <Trans i18nKey="b" />;
+    };
+
+    export function Second() {
+        const { t } = useTranslation('react');
+        function Nested() {
+            const { t } = useTranslation('noArgs');
+            return <Trans i18nKey="x.y" />;
+        }
+        console.log(t('foo'));
+        return <Trans i18nKey="bar" />;
+    }
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    key_prefix,
+    r#"
+    export const keyPrefix = () => {
+        const { t } = useTranslation('noArgs', { keyPrefix: 'x' });
+        console.log(t('y'));
+        return <Trans i18nKey="a" />;
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| locale_transformer("en", &[]),
+    lng,
+    r#"
+    import i18next from "i18next";
+
+    export const lng = ({ count }) => {
+        const { t } = useTranslation('common', { lng: 'de' });
+        console.log(t('greeting'));
+        console.log(t('apple', { count }));
+    };
+
+    export const defaultLocale = () => {
+        const { t } = useTranslation('common');
+        console.log(t('greeting'));
+    };
+
+    const de = i18next.getFixedT('de', 'common');
+    export const fixedLng = () => de('greeting');
+
+    export async function awaitedLng() {
+        const { t } = await getTranslation('de', 'common');
+        return t('greeting');
+    }
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(config()),
+    instances,
+    r#"
+    import i18next from "i18next";
+    const i18n = require("./i18n");
+
+    const t = i18next.getFixedT(null, 'noArgs');
+    const prefixed = i18next.getFixedT(undefined, ['noArgs', 'noArgs2'], 'x');
+    const defaults = i18next.getFixedT(null);
+
+    export const errors = () => i18next.t('noArgs2:c');
+    export const plural = (count) => i18n.t('plural:item', { count });
+    export const fixed = () => [t('a'), t('noArgs2:d'), prefixed('y')];
+    export const defaultNs = () => [i18next.t('hello'), defaults('hello')];
+    export const shadowed = (i18next) => i18next.t('hello');
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    component_scopes,
+    r#"
+    export const ComponentScopes = () => {
+        const { t } = useTranslation('react');
+        return (
+          <div>
+            <Trans components={{ link: <Link href="foo.com" />, button: <Button /> }}>
+              {t('transComponents')}
+            </Trans>
+            <Trans components={{ link: <a href="bar.com" /> }}>{t('transComponents')}</Trans>
+            <Trans>{t('transComponents')}</Trans>
+          </div>
+        );
+    };
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    wrappers,
+    r#"
+    import { withTranslation as withT, Translation } from "react-i18next";
+
+    class Greeting extends React.Component {
+        render() {
+            const { t } = this.props;
+            return <p>{t('a')} {this.props.t('b')}</p>;
+        }
+    }
+
+    function Prefixed(props) {
+        return <p>{props.t('y')}</p>;
+    }
+
+    export const Render = () => (
+        <div>
+            <Translation ns="noArgs2">{(t) => <p>{t('c')}</p>}</Translation>
+        </div>
+    );
+
+    export default withT('noArgs')(Greeting);
+    export const PrefixedWithTranslation = withT('noArgs', { keyPrefix: 'x' })(Prefixed);
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    wrappers_runtime,
+    r#"
+    import { withTranslation, Translation } from "react-i18next";
+
+    const Passed = ({ t }) => <Child label={t('a')} t={t} />;
+
+    export const Render = () => (
+        <Translation ns="noArgs2">{(t) => <Child label={t('c')} t={t} />}</Translation>
+    );
+
+    export default withTranslation('noArgs')(Passed);
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    async_hooks,
+    r#"
+    import { getTranslation } from "@/i18n/server";
+
+    export default async function Page({ params: { lng } }) {
+        const { t } = await getTranslation(lng, 'noArgs');
+        return <p>{t('a')}</p>;
+    }
+
+    export async function Language({ lng }) {
+        const { t, i18n } = await getTranslation(lng, ['noArgs', 'noArgs2'], { keyPrefix: 'x' });
+        return <p lang={i18n.language}>{t('y')}</p>;
+    }
+
+    export async function Messages() {
+        const t = await getTranslations('noArgs2');
+        return <p>{t('c')}</p>;
+    }
+    "#
+);
+
+test!(
+    syntax(true),
+    |_| visitor(config()),
+    unused_bindings,
+    r#"
+    import { useTranslation, Trans, I18nextProvider, initReactI18next } from "react-i18next";
+
+    i18next.use(initReactI18next);
+
+    export const Provider = ({ children }) => (
+        <I18nextProvider i18n={i18next}>{children}</I18nextProvider>
+    );
+
+    export const Component = () => {
+        const { t, i18n } = useTranslation('noArgs');
+        const [translate, instance] = useTranslation('noArgs2');
+        return <p lang={i18n.language} dir={instance.dir()}>{t('a')}{translate('c')}</p>;
+    };
+    "#
+);
+
+test!(
+    syntax(false),
+    |_| visitor(config()),
+    require,
+    r#"
+    const { useTranslation: useT } = require("react-i18next");
+    const { initReactI18next } = require("react-i18next");
+
+    exports.init = () => i18next.use(initReactI18next);
+    exports.message = () => {
+        const { t } = useT('noArgs');
+        return t('a');
+    };
+    "#
+);
+
+#[test]
+fn fallback_report() {
+    let report = std::env::temp_dir().join(format!("fallbacks-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&report);
+    // rebuilds rewrite the report without duplicating keys
+    for _ in 0..2 {
+        test_transform(
+            syntax(false),
+            |_| {
+                visitor(Config {
+                    base_dir: fixture_dir("locales"),
+                    locale: "de".to_string(),
+                    fallback_locales: vec!["en".to_string()],
+                    fallback_report: Some(report.to_str().unwrap().to_string()),
+                    path: "{baseDir}/{locale}/{ns}.json".to_string(),
+                    ..Default::default()
+                })
+            },
+            r#"
+            export const fallbackLocales = () => {
+                const { t } = useTranslation('common');
+                console.log(t('greeting'), t('farewell'));
+            };
+            "#,
+            r#"
+            export const fallbackLocales = () => {
+                console.log("Hallo", "Goodbye");
+            };
+            "#,
+            false,
+        );
+    }
+    assert_eq!(
+        std::fs::read_to_string(&report).unwrap(),
+        "{\"from\":\"en\",\"key\":\"farewell\",\"locale\":\"de\",\"namespace\":\"common\"}\n"
+    );
+    std::fs::remove_file(&report).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "t is used out of scope of useTranslation")]
+fn out_of_scope() {
+    test_transform(
+        syntax(false),
+        |_| visitor(config()),
+        r#"
+        export const outOfScope = () => {
+            console.log(t('a'));
+        };
+        "#,
+        "",
+        false,
+    );
+}
+
+#[test]
+#[should_panic(expected = "missing variables for key: object:foo: max")]
+fn missing_variables() {
+    test_transform(
+        syntax(false),
+        |_| {
+            visitor(Config {
+                variable_check: VariableCheck::Error,
+                ..config()
+            })
+        },
+        r#"
+        export const object = ({ min }) => {
+            const { t } = useTranslation('object');
+            console.log(t('foo', { min }));
+        };
+        "#,
+        "",
+        false,
+    );
+}
+
+#[test]
+#[should_panic(expected = "unused variables for key: object:foo: step")]
+fn unused_variables() {
+    test_transform(
+        syntax(false),
+        |_| {
+            visitor(Config {
+                variable_check: VariableCheck::Error,
+                ..config()
+            })
+        },
+        r#"
+        export const object = ({ min, max, step }) => {
+            const { t } = useTranslation('object');
+            console.log(t('foo', { min, max, step }));
+        };
+        "#,
+        "",
+        false,
+    );
+}
+
+#[test]
+fn fallback_warnings() {
+    let warnings = warnings(
+        Config {
+            base_dir: fixture_dir("locales"),
+            locale: "de".to_string(),
+            fallback_locales: vec!["en".to_string()],
+            path: "{baseDir}/{locale}/{ns}.json".to_string(),
+            ..Default::default()
+        },
+        r#"
+        export const fallbackLocales = () => {
+            const { t } = useTranslation('common');
+            console.log(t('greeting'), t('farewell'), t('farewell'));
+        };
+        "#,
+    );
+    assert_eq!(
+        warnings,
+        vec!["key: common:farewell is missing in de and served from en"]
+    );
+}
+
+#[test]
+fn default_value_warnings() {
+    let warnings = warnings(
+        config(),
+        r#"
+        export const defaultValue = () => {
+            const { t } = useTranslation('noArgs');
+            console.log(t('a', 'test1'), t('a', 'Different'));
+        };
+        "#,
+    );
+    assert_eq!(
+        warnings,
+        vec![r#"default value of key: noArgs:a differs from catalogs. "Different" != "test1""#]
+    );
+}
+
+#[test]
+fn trans_components_warnings() {
+    let warnings = warnings(
+        config(),
+        r#"
+        const Greeting = ({ name }) => {
+            const { t } = useTranslation('react');
+            return (
+                <>
+                    <Trans i18nKey="greeting" values={{ name }} components={{ bold: <strong /> }} />
+                    <Trans i18nKey="greeting" values={{ name }} />
+                </>
+            );
+        };
+        "#,
+    );
+    assert_eq!(
+        warnings,
+        vec![r#"<bold> in "Hello <bold>{{name}}</bold>!" has no mapping"#]
+    );
+}
+
+#[test]
+fn variable_check_warnings() {
+    let warnings = warnings(
+        Config {
+            variable_check: VariableCheck::Warn,
+            ..config()
+        },
+        r#"
+        export const object = ({ min, step }) => {
+            const { t } = useTranslation('object');
+            console.log(t('foo', { min, step }));
+        };
+        "#,
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            "missing variables for key: object:foo: max",
+            "unused variables for key: object:foo: step",
+        ]
+    );
+}
@@ -1,31 +1,317 @@
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
 use swc_core::{
-    common::{FileName, FilePathMapping, SourceMap, Span},
-    ecma::ast::Expr,
+    common::Span,
+    ecma::ast::{
+        Expr, Id, Ident, JSXAttrValue, JSXExpr, Lit, ObjectLit, ObjectPatProp, Pat, Prop, PropName,
+        PropOrSpread,
+    },
     plugin::errors::HANDLER,
 };
-use swc_ecma_parser::{parse_file_as_expr, EsConfig, PResult, Syntax};
 
-pub fn json_path_from_key(value: String, scopes: &[String]) -> (String, String) {
-    if let Some(keys) = value.split_once(':') {
-        (keys.0.to_string(), keys.1.to_string())
-    } else {
-        (scopes.get(0).unwrap_or(&"".to_string()).clone(), value)
+pub fn json_path_from_key(
+    value: String,
+    scopes: &[String],
+    key_prefix: Option<&str>,
+    ns_separator: Option<&str>,
+    key_separator: Option<&str>,
+) -> (String, String) {
+    let (filename, key) =
+        if let Some(keys) = ns_separator.and_then(|separator| value.split_once(separator)) {
+            (keys.0.to_string(), keys.1.to_string())
+        } else {
+            (scopes.first().unwrap_or(&"".to_string()).clone(), value)
+        };
+    match key_prefix {
+        // e.g) useTranslation("ns", { keyPrefix: "foo" }) and t("bar") -> foo.bar
+        // flat keys are joined by "." as i18next does
+        Some(prefix) => (
+            filename,
+            format!("{}{}{}", prefix, key_separator.unwrap_or("."), key),
+        ),
+        None => (filename, key),
+    }
+}
+
+/// Returns bindings of translation functions destructured from a hook
+/// e.g) const { t } = ..., const { t: translate } = ..., const [t] = ...
+pub fn translation_bindings(pat: &Pat, method_names: &HashSet<String>) -> Vec<Id> {
+    match pat {
+        Pat::Object(obj) => obj
+            .props
+            .iter()
+            .filter_map(|prop| match prop {
+                ObjectPatProp::Assign(assign) if method_names.contains(&*assign.key.id.sym) => {
+                    Some(assign.key.id.to_id())
+                }
+                ObjectPatProp::KeyValue(kv) => match (&kv.key, &*kv.value) {
+                    (PropName::Ident(key), Pat::Ident(binding))
+                        if method_names.contains(&*key.sym) =>
+                    {
+                        Some(binding.id.to_id())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+        Pat::Array(array) => array
+            .elems
+            .first()
+            .and_then(|el| el.as_ref())
+            .and_then(|el| el.as_ident())
+            .map(|binding| vec![binding.id.to_id()])
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// Returns a string value of jsx attribute e.g) i18nKey="foo" or i18nKey={"foo"}
+pub fn jsx_attr_str(value: &Option<JSXAttrValue>) -> Option<String> {
+    match jsx_attr_expr(value).map(|expr| *expr) {
+        Some(Expr::Lit(Lit::Str(lit))) => Some(lit.value.to_string()),
+        Some(Expr::Tpl(tpl)) if tpl.exprs.is_empty() => {
+            tpl.quasis.first().map(|quasi| quasi.raw.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns an expression of jsx attribute e.g) values={{ name }}
+pub fn jsx_attr_expr(value: &Option<JSXAttrValue>) -> Option<Box<Expr>> {
+    match value {
+        Some(JSXAttrValue::Lit(lit)) => Some(Box::new(Expr::Lit(lit.clone()))),
+        Some(JSXAttrValue::JSXExprContainer(c)) => match &c.expr {
+            JSXExpr::Expr(expr) => Some(expr.clone()),
+            JSXExpr::JSXEmptyExpr(_) => None,
+        },
+        _ => None,
+    }
+}
+
+/// Collects properties of object literal e.g) { name, age: 10 }
+pub fn props_from_object(obj: &ObjectLit) -> HashMap<String, Box<Expr>> {
+    let mut props = HashMap::new();
+    for prop in &obj.props {
+        let PropOrSpread::Prop(prop) = prop else {
+            continue;
+        };
+        match &**prop {
+            Prop::Shorthand(ident) => {
+                props.insert(ident.sym.to_string(), Box::new(Expr::Ident(ident.clone())));
+            }
+            Prop::KeyValue(kv) => {
+                let key = match &kv.key {
+                    PropName::Ident(Ident { sym, .. }) => sym.to_string(),
+                    PropName::Str(str) => str.value.to_string(),
+                    _ => continue,
+                };
+                props.insert(key, kv.value.clone());
+            }
+            _ => {}
+        }
+    }
+    props
+}
+
+/// Removes a property from object literal e.g) { name, defaultValue: "foo" } -> { name }
+pub fn remove_prop(obj: &mut ObjectLit, name: &str) {
+    obj.props.retain(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return true;
+        };
+        match &**prop {
+            Prop::Shorthand(ident) => &*ident.sym != name,
+            Prop::KeyValue(kv) => match &kv.key {
+                PropName::Ident(Ident { sym, .. }) => &**sym != name,
+                PropName::Str(str) => &*str.value != name,
+                _ => true,
+            },
+            _ => true,
+        }
+    });
+}
+
+/// Returns `count` of options for t() e.g) t("foo", { count: 1 })
+pub fn count_from_options(options: &Expr) -> Option<Box<Expr>> {
+    match options {
+        Expr::Object(obj) => props_from_object(obj).remove("count"),
+        _ => None,
+    }
+}
+
+/// Returns `context` of options for t() e.g) t("foo", { context: "male" })
+pub fn context_from_options(options: &Expr) -> Option<Box<Expr>> {
+    match options {
+        Expr::Object(obj) => props_from_object(obj).remove("context"),
+        _ => None,
+    }
+}
+
+/// Returns `defaultValue` of options for t() e.g) t("foo", { defaultValue: "Foo" })
+pub fn default_value_from_options(options: &Expr) -> Option<Box<Expr>> {
+    match options {
+        Expr::Object(obj) => props_from_object(obj).remove("defaultValue"),
+        _ => None,
+    }
+}
+
+/// Returns the value of a number literal as a string e.g) 0x1 -> "1", 1_000 -> "1000", 1.0 -> "1"
+/// JS numbers have no visible fraction digits, so 1.0 selects "one" as Intl.PluralRules does
+pub fn number_from_expr(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Num(num)) => Some(num.value.to_string()),
+        _ => None,
+    }
+}
+
+/// A text or a placeholder of a message
+#[derive(Debug, PartialEq)]
+pub enum Segment {
+    Text(String),
+    /// e.g) {{price, currency(USD)}}, {{- html}}
+    Placeholder {
+        name: String,
+        formats: Vec<String>,
+        /// unescaped by the prefix e.g) {{- html}}
+        raw: bool,
+    },
+}
+
+/// Splits a message into texts and placeholders
+/// e.g) "{{price, currency(USD)}} only" -> [Placeholder("price", ["currency(USD)"]), Text(" only")]
+pub fn tokenize(message: &str) -> Vec<Segment> {
+    let re = Regex::new(r"\{\{(.+?)\}\}").unwrap();
+    let mut segments = vec![];
+    let mut last = 0;
+    for caps in re.captures_iter(message) {
+        let all = caps.get(0).unwrap();
+        if all.start() > last {
+            segments.push(Segment::Text(message[last..all.start()].to_string()));
+        }
+        last = all.end();
+        let mut parts = vec![];
+        let mut depth = 0;
+        let mut start = 0;
+        let placeholder = &caps[1];
+        for (i, c) in placeholder.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(placeholder[start..i].trim().to_string());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(placeholder[start..].trim().to_string());
+        let name = parts.remove(0);
+        let (name, raw) = match name.strip_prefix('-') {
+            Some(name) => (name.trim().to_string(), true),
+            None => (name, false),
+        };
+        segments.push(Segment::Placeholder {
+            name,
+            formats: parts,
+            raw,
+        });
+    }
+    if last < message.len() {
+        segments.push(Segment::Text(message[last..].to_string()));
+    }
+    segments
+}
+
+/// A node of a message of <Trans /> e.g) "Hello <bold>{{name}}</bold>"
+#[derive(Debug, PartialEq)]
+pub enum MessageNode {
+    Segment(Segment),
+    /// e.g) <bold>...</bold>, <0>...</0>, <br />
+    Element {
+        name: String,
+        children: Vec<MessageNode>,
+    },
+}
+
+/// Parses tags of a message into elements leaving the rest as texts and placeholders
+/// e.g) "Hello <bold>{{name}}</bold>" -> [Text("Hello "), Element("bold", [Placeholder("name")])]
+/// a "<" out of tags is a text e.g) "1 < 2"
+pub fn parse_message(message: &str) -> Result<Vec<MessageNode>, String> {
+    let re = Regex::new(r"<(/?)([A-Za-z0-9_]+)\s*(/?)>").unwrap();
+    let mut stack: Vec<(String, Vec<MessageNode>)> = vec![(String::new(), vec![])];
+    let push = |stack: &mut Vec<(String, Vec<MessageNode>)>, node| {
+        stack.last_mut().unwrap().1.push(node);
+    };
+    for segment in tokenize(message) {
+        let Segment::Text(text) = segment else {
+            push(&mut stack, MessageNode::Segment(segment));
+            continue;
+        };
+        let mut last = 0;
+        for caps in re.captures_iter(&text) {
+            let all = caps.get(0).unwrap();
+            if all.start() > last {
+                let text = text[last..all.start()].to_string();
+                push(&mut stack, MessageNode::Segment(Segment::Text(text)));
+            }
+            last = all.end();
+            let name = caps[2].to_string();
+            match (&caps[1], &caps[3]) {
+                ("", "") => stack.push((name, vec![])),
+                // e.g) <br />
+                ("", _) => push(
+                    &mut stack,
+                    MessageNode::Element {
+                        name,
+                        children: vec![],
+                    },
+                ),
+                (_, "") => {
+                    if stack.len() == 1 || stack.last().unwrap().0 != name {
+                        return Err(format!("unexpected </{}>", name));
+                    }
+                    let (name, children) = stack.pop().unwrap();
+                    push(&mut stack, MessageNode::Element { name, children });
+                }
+                _ => return Err(format!("invalid tag {}", all.as_str())),
+            }
+        }
+        if last < text.len() {
+            let text = text[last..].to_string();
+            push(&mut stack, MessageNode::Segment(Segment::Text(text)));
+        }
+    }
+    if stack.len() > 1 {
+        return Err(format!("<{}> is not closed", stack.last().unwrap().0));
+    }
+    Ok(stack.pop().unwrap().1)
+}
+
+/// Escapes a value for HTML as i18next does e.g) "<b>" -> "&lt;b&gt;"
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            '/' => escaped.push_str("&#x2F;"),
+            c => escaped.push(c),
+        }
     }
+    escaped
 }
 
-pub fn parse_code(code: String) -> PResult<Box<Expr>> {
-    let cm = SourceMap::new(FilePathMapping::empty());
-    let fm = cm.new_source_file(FileName::Custom("".into()), code);
-    parse_file_as_expr(
-        &fm,
-        Syntax::Es(EsConfig {
-            jsx: true,
-            ..Default::default()
-        }),
-        Default::default(),
-        None,
-        &mut vec![],
-    )
+/// Escapes a text to be a raw string of a template literal e.g) "`a` ${b}" -> "\\`a\\` \\${b}"
+pub fn escape_template(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+        .replace('\r', "\\r")
 }
 
 pub fn emit_error(message: &str, span: Span) {
@@ -37,15 +323,188 @@ pub fn emit_error(message: &str, span: Span) {
     });
 }
 
+pub fn emit_warning(message: &str, span: Span) {
+    HANDLER.with(|handler| {
+        handler.struct_span_warn(span, message).emit();
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use swc_core::common::{FileName, FilePathMapping, SourceMap};
+    use swc_ecma_parser::{parse_file_as_expr, EsConfig, PResult, Syntax};
+
+    fn parse_code(code: String) -> PResult<Box<Expr>> {
+        let cm = SourceMap::new(FilePathMapping::empty());
+        let fm = cm.new_source_file(FileName::Custom("".into()), code);
+        parse_file_as_expr(
+            &fm,
+            Syntax::Es(EsConfig {
+                jsx: true,
+                ..Default::default()
+            }),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+    }
 
     #[test]
     fn test_json_path_from_key() {
         let scopes = vec!["module".to_string(), "data".to_string()];
-        let result = json_path_from_key("variable:name".to_string(), &scopes);
+        let result = json_path_from_key(
+            "variable:name".to_string(),
+            &scopes,
+            None,
+            Some(":"),
+            Some("."),
+        );
         assert_eq!(result, ("variable".to_string(), "name".to_string()));
+        let result = json_path_from_key("variable:name".to_string(), &scopes, None, None, None);
+        assert_eq!(result, ("module".to_string(), "variable:name".to_string()));
+        let result = json_path_from_key(
+            "name".to_string(),
+            &scopes,
+            Some("form"),
+            Some(":"),
+            Some("."),
+        );
+        assert_eq!(result, ("module".to_string(), "form.name".to_string()));
+        let result = json_path_from_key(
+            "data:name".to_string(),
+            &scopes,
+            Some("form"),
+            Some(":"),
+            None,
+        );
+        assert_eq!(result, ("data".to_string(), "form.name".to_string()));
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let text = |text: &str| MessageNode::Segment(Segment::Text(text.to_string()));
+        let placeholder = |name: &str| {
+            MessageNode::Segment(Segment::Placeholder {
+                name: name.to_string(),
+                formats: vec![],
+                raw: false,
+            })
+        };
+        assert_eq!(
+            parse_message("Hello <bold>{{name}}</bold>!<br/>"),
+            Ok(vec![
+                text("Hello "),
+                MessageNode::Element {
+                    name: "bold".to_string(),
+                    children: vec![placeholder("name")],
+                },
+                text("!"),
+                MessageNode::Element {
+                    name: "br".to_string(),
+                    children: vec![],
+                },
+            ])
+        );
+        assert_eq!(
+            parse_message("<0>1 < 2</0> {fetch()} `a`"),
+            Ok(vec![
+                MessageNode::Element {
+                    name: "0".to_string(),
+                    children: vec![text("1 < 2")],
+                },
+                text(" {fetch()} `a`"),
+            ])
+        );
+        assert_eq!(
+            parse_message("<b>bold</i>"),
+            Err("unexpected </i>".to_string())
+        );
+        assert_eq!(
+            parse_message("<b>bold"),
+            Err("<b> is not closed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("{{price, currency(USD)}} at {{date, datetime(month: long; day: 2)}}"),
+            vec![
+                Segment::Placeholder {
+                    name: "price".to_string(),
+                    formats: vec!["currency(USD)".to_string()],
+                    raw: false,
+                },
+                Segment::Text(" at ".to_string()),
+                Segment::Placeholder {
+                    name: "date".to_string(),
+                    formats: vec!["datetime(month: long; day: 2)".to_string()],
+                    raw: false,
+                },
+            ]
+        );
+        assert_eq!(
+            tokenize("hello {{ name }}!{{- html }}"),
+            vec![
+                Segment::Text("hello ".to_string()),
+                Segment::Placeholder {
+                    name: "name".to_string(),
+                    formats: vec![],
+                    raw: false,
+                },
+                Segment::Text("!".to_string()),
+                Segment::Placeholder {
+                    name: "html".to_string(),
+                    formats: vec![],
+                    raw: true,
+                },
+            ]
+        );
+        assert_eq!(tokenize(""), vec![]);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html(r#"<a href="/">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;&#x2F;&quot;&gt;Tom &amp; Jerry&#39;s&lt;&#x2F;a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_escape_template() {
+        assert_eq!(escape_template(r"`a` ${b} c:\d"), r"\`a\` \${b} c:\\d");
+        assert_eq!(escape_template("$a {b}"), "$a {b}");
+    }
+
+    #[test]
+    fn test_remove_prop() {
+        let Ok(expr) = parse_code("({ name, defaultValue: 'foo', 'count': 1 })".to_string()) else {
+            panic!("failed to parse");
+        };
+        let Expr::Paren(paren) = *expr else {
+            panic!("not a paren");
+        };
+        let Expr::Object(mut obj) = *paren.expr else {
+            panic!("not an object");
+        };
+        remove_prop(&mut obj, "defaultValue");
+        remove_prop(&mut obj, "count");
+        let props = props_from_object(&obj);
+        assert_eq!(props.len(), 1);
+        assert!(props.contains_key("name"));
+    }
+
+    #[test]
+    fn test_number_from_expr() {
+        let number = |code: &str| number_from_expr(&parse_code(code.to_string()).unwrap());
+        assert_eq!(number("1").as_deref(), Some("1"));
+        assert_eq!(number("0x1").as_deref(), Some("1"));
+        assert_eq!(number("1_000").as_deref(), Some("1000"));
+        assert_eq!(number("1.0").as_deref(), Some("1"));
+        assert_eq!(number("1.5").as_deref(), Some("1.5"));
+        assert_eq!(number("count"), None);
     }
 
     #[test]
@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+
+use swc_core::ecma::{
+    ast::{
+        ArrowExpr, BlockStmtOrExpr, CallExpr, Callee, Expr, ExprOrSpread, Id, JSXElement,
+        JSXElementChild, JSXExpr, JSXExprContainer, MemberExpr, MemberProp, Pat, Prop,
+        VarDeclarator,
+    },
+    visit::{noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith},
+};
+
+/// Returns the HOC and the component of withTranslation("ns")(Component)
+fn wrapped_call<'a>(call: &'a CallExpr, hoc_names: &HashSet<String>) -> Option<(&'a CallExpr, Id)> {
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+    let Expr::Call(hoc) = &**callee else {
+        return None;
+    };
+    let Callee::Expr(hoc_callee) = &hoc.callee else {
+        return None;
+    };
+    let Expr::Ident(hoc_ident) = &**hoc_callee else {
+        return None;
+    };
+    if !hoc_names.contains(&*hoc_ident.sym) {
+        return None;
+    }
+    match call.args.first().map(|arg| &*arg.expr) {
+        Some(Expr::Ident(component)) if call.args.len() == 1 => Some((hoc, component.to_id())),
+        _ => None,
+    }
+}
+
+pub struct WrappedCollector<'a> {
+    hoc_names: &'a HashSet<String>,
+    components: Vec<(Id, Vec<ExprOrSpread>)>,
+}
+
+impl Visit for WrappedCollector<'_> {
+    noop_visit_type!();
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if let Some((hoc, component)) = wrapped_call(n, self.hoc_names) {
+            self.components.push((component, hoc.args.clone()));
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// Returns components wrapped by HOCs with arguments of the HOCs
+/// e.g) withTranslation("common")(Component) -> [(Component, ["common"])]
+pub fn wrapped_components<N: for<'a> VisitWith<WrappedCollector<'a>>>(
+    n: &N,
+    hoc_names: &HashSet<String>,
+) -> Vec<(Id, Vec<ExprOrSpread>)> {
+    let mut collector = WrappedCollector {
+        hoc_names,
+        components: vec![],
+    };
+    n.visit_with(&mut collector);
+    collector.components
+}
+
+/// Replaces withTranslation("ns")(Component) with Component whose translations are inlined
+pub struct Unwrapper<'a> {
+    pub hoc_names: &'a HashSet<String>,
+    pub components: &'a HashSet<Id>,
+}
+
+impl VisitMut for Unwrapper<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        n.visit_mut_children_with(self);
+        if let Expr::Call(call) = n {
+            if let Some((_, component)) = wrapped_call(call, self.hoc_names) {
+                if self.components.contains(&component) {
+                    *n = *call.args[0].expr.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Returns true for props of a component e.g) this.props, props of (props) => ...
+pub fn is_props(expr: &Expr, props: Option<&Id>) -> bool {
+    match expr {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => obj.is_this() && &prop.sym == "props",
+        Expr::Ident(ident) => props == Some(&ident.to_id()),
+        _ => false,
+    }
+}
+
+/// Finds translation functions left in a component e.g) t, this.props.t, props.t, {...props}
+pub struct References<'a> {
+    pub ids: &'a HashSet<Id>,
+    pub method_names: &'a HashSet<String>,
+    /// props of a function component e.g) (props) => ...
+    pub props: Option<&'a Id>,
+    pub found: bool,
+}
+
+impl Visit for References<'_> {
+    noop_visit_type!();
+
+    fn visit_expr(&mut self, n: &Expr) {
+        match n {
+            // e.g) <Child {...props} /> may pass t
+            expr if is_props(expr, self.props) => self.found = true,
+            Expr::Ident(ident) if self.ids.contains(&ident.to_id()) => self.found = true,
+            _ => n.visit_children_with(self),
+        }
+    }
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if !is_props(&n.obj, self.props) {
+            return n.visit_children_with(self);
+        }
+        match &n.prop {
+            MemberProp::Ident(prop) => self.found |= self.method_names.contains(&*prop.sym),
+            prop => prop.visit_with(self),
+        }
+    }
+
+    fn visit_prop(&mut self, n: &Prop) {
+        match n {
+            // e.g) { t }
+            Prop::Shorthand(ident) if self.ids.contains(&ident.to_id()) => self.found = true,
+            _ => n.visit_children_with(self),
+        }
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        // e.g) const { t } = this.props binds t tracked by ids
+        if !n
+            .init
+            .as_deref()
+            .is_some_and(|init| is_props(init, self.props))
+        {
+            n.visit_children_with(self);
+        }
+    }
+}
+
+/// Returns a render function of <Translation>{(t) => ...}</Translation>
+pub fn render_prop(n: &JSXElement) -> Option<&ArrowExpr> {
+    let mut children = n.children.iter().filter(|child| match child {
+        JSXElementChild::JSXText(text) => !text.value.trim().is_empty(),
+        _ => true,
+    });
+    match (children.next(), children.next()) {
+        (
+            Some(JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            })),
+            None,
+        ) => expr.as_arrow(),
+        _ => None,
+    }
+}
+
+/// Returns a body of the render function whose translations are all inlined
+/// e.g) <Translation>{(t) => <p>{"foo"}</p>}</Translation> -> <p>{"foo"}</p>
+pub fn render_prop_body(n: &JSXElement, method_names: &HashSet<String>) -> Option<Box<Expr>> {
+    let arrow = render_prop(n)?;
+    let [Pat::Ident(t)] = arrow.params.as_slice() else {
+        return None;
+    };
+    let BlockStmtOrExpr::Expr(body) = &*arrow.body else {
+        return None;
+    };
+    let mut references = References {
+        ids: &HashSet::from([t.id.to_id()]),
+        method_names,
+        props: None,
+        found: false,
+    };
+    body.visit_with(&mut references);
+    (!references.found).then(|| body.clone())
+}
@@ -0,0 +1,16 @@
+import { getTranslation } from "@/i18n/server";
+export default async function Page({ params: { lng } }) {
+    return <p>{"test1"}</p>;
+}
+export async function Language({ lng }) {
+    const { i18n } = await getTranslation(lng, [
+        'noArgs',
+        'noArgs2'
+    ], {
+        keyPrefix: 'x'
+    });
+    return <p lang={i18n.language}>{"test2"}</p>;
+}
+export async function Messages() {
+    return <p>{"test3"}</p>;
+}
@@ -0,0 +1,15 @@
+const t = (key)=>key;
+export const renamed = ()=>{
+    console.log("test1");
+    console.log(t('a'));
+    useEffect(()=>{}, [
+        t
+    ]);
+};
+export const array = ()=>{
+    console.log("line1\nline2");
+    const shadowed = (t)=>t('a');
+};
+export const unrelated = ()=>{
+    console.log(t('a'));
+};
@@ -0,0 +1,14 @@
+export const po = ({ count })=>{
+    console.log("Hello John");
+    console.log("Good day John");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("en").select(v.count)){
+            case "one":
+                return `${v.count} apple`;
+            default:
+                return `${v.count} apples`;
+        }
+    })({
+        count
+    }));
+};
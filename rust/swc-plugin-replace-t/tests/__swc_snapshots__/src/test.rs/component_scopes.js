@@ -0,0 +1,15 @@
+export const ComponentScopes = ()=>{
+    return <div>
+
+            <>
+
+              {<><Link href="foo.com">aaaaa</Link><Button/></>}
+
+            </>
+
+            <>{<><a href="bar.com">aaaaa</a><button/></>}</>
+
+            <>{<><link>aaaaa</link><button/></>}</>
+
+          </div>;
+};
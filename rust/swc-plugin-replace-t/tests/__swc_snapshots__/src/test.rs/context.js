@@ -0,0 +1,33 @@
+export const context = ({ gender, count })=>{
+    console.log("A boyfriend");
+    console.log("2 girlfriends");
+    console.log("A friend");
+    console.log(((v)=>{
+        switch(v.context){
+            case "female":
+                switch(new Intl.PluralRules("en").select(v.count)){
+                    case "one":
+                        return `A girlfriend`;
+                    default:
+                        return `${v.count} girlfriends`;
+                }
+            case "male":
+                return `A boyfriend`;
+            default:
+                return `A friend`;
+        }
+    })({
+        context: gender,
+        count
+    }));
+    console.log(((v)=>{
+        switch(v.context){
+            case "formal":
+                return `Good afternoon`;
+            default:
+                return `Hi`;
+        }
+    })({
+        context: gender
+    }));
+};
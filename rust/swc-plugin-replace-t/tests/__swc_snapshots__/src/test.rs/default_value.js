@@ -0,0 +1,8 @@
+export const defaultValue = ({ name })=>{
+    console.log("test1");
+    console.log("test1");
+    console.log('Default text');
+    console.log(`Hello ${name}`);
+    console.log(`Hi ${name}`);
+    console.log("test2");
+};
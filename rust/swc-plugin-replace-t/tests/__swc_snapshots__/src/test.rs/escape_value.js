@@ -0,0 +1,15 @@
+"use client";
+import escape from 'escape-html';
+const escape1 = (value)=>String(value).replace(/[&<>"'\/]/g, (c)=>({
+            '&': '&amp;',
+            '<': '&lt;',
+            '>': '&gt;',
+            '"': '&quot;',
+            "'": '&#39;',
+            '/': '&#x2F;'
+        })[c]);
+export const object = ({ html })=>{
+    console.log(`${html} or ${escape1(html)}`);
+    console.log("<b>Tom & Jerry</b> or &lt;b&gt;Tom &amp; Jerry&lt;&#x2F;b&gt;");
+    console.log(escape(html));
+};
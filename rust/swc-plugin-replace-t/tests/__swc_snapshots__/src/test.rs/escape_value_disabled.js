@@ -0,0 +1,3 @@
+export const object = ({ html })=>{
+    console.log(`${html} or ${html}`);
+};
@@ -0,0 +1,14 @@
+export const fallbackLocales = ({ count })=>{
+    console.log("Hallo");
+    console.log("Goodbye");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("de").select(v.count)){
+            case "one":
+                return `${v.count} Apfel`;
+            default:
+                return `${v.count} Äpfel`;
+        }
+    })({
+        count
+    }));
+};
@@ -0,0 +1,17 @@
+export const format = ({ price, date, diff, names })=>{
+    console.log(`Price: ${new Intl.NumberFormat("en", {
+        currency: "USD",
+        style: "currency"
+    }).format(price)}`);
+    console.log(`${new Intl.NumberFormat("en", {}).format(1)} of ${new Intl.NumberFormat("en", {
+        minimumFractionDigits: 2
+    }).format(10)}`);
+    console.log(`Updated ${new Intl.DateTimeFormat("en", {
+        day: "numeric",
+        month: "long"
+    }).format(date)}`);
+    console.log(`${new Intl.RelativeTimeFormat("en", {}).format(diff, "day")}`);
+    console.log(`${new Intl.ListFormat("en", {
+        type: "disjunction"
+    }).format(names)}`);
+};
@@ -0,0 +1,5 @@
+import { useTranslation } from "react-i18next";
+export const identifiers = ({ name })=>{
+    console.log("hi");
+    return <>Hello <b>{name}</b>!</>;
+};
@@ -0,0 +1,23 @@
+import i18next from "i18next";
+const i18n = require("./i18n");
+export const errors = ()=>"test3";
+export const plural = (count)=>((v)=>{
+        switch(new Intl.PluralRules("en").select(v.count)){
+            case "one":
+                return `${v.count} item`;
+            default:
+                return `${v.count} items`;
+        }
+    })({
+        count
+    });
+export const fixed = ()=>[
+        "test1",
+        "test4",
+        "test2"
+    ];
+export const defaultNs = ()=>[
+        "Hello",
+        "Hello"
+    ];
+export const shadowed = (i18next)=>i18next.t('hello');
@@ -1,6 +1,3 @@
 export const object = ()=>{
-    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
-        min: 100,
-        max: 400
-    }));
+    console.log("value: 400 - 100");
 };
@@ -0,0 +1,3 @@
+export const object = ()=>{
+    console.log("`foo` costs $100 in C:\\tmp");
+};
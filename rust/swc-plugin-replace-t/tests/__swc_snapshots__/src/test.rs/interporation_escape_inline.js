@@ -0,0 +1,3 @@
+export const object = ({ name, price, dir })=>{
+    console.log(`\`${name}\` costs $${price} in C:\\${dir}`);
+};
@@ -0,0 +1,21 @@
+export const object = ({ min, max, range, values })=>{
+    console.log(`value: ${max} - ${min}`);
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
+        min: range.min,
+        max: range.max
+    }));
+    console.log("value: 2.5 - 1");
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
+        min: getMin(),
+        max
+    }));
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
+        min
+    }));
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
+        ...values,
+        min,
+        max
+    }));
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)(values));
+};
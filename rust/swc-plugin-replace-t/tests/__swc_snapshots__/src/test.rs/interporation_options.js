@@ -0,0 +1,10 @@
+export const object = (props)=>{
+    const { t } = useTranslation('object');
+    console.log(((v)=>`${v.user.name} lives in ${v.user.address.city}`)({
+        user: props.user
+    }));
+    console.log(((v)=>`${v.user.name} lives in ${v.user.address.city}`)(props.options));
+    console.log(((v)=>`${v.user.name} lives in ${v.user.address.city}`)(getOptions()));
+    console.log(((v)=>`${v.user.name} lives in ${v.user.address.city}`)(getOptions()));
+    console.log(t('dotted', ...args));
+};
@@ -0,0 +1,4 @@
+export const keyPrefix = ()=>{
+    console.log("test2");
+    return <>{"test1"}</>;
+};
@@ -0,0 +1,21 @@
+import i18next from "i18next";
+export const lng = ({ count })=>{
+    console.log("Hallo");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("de").select(v.count)){
+            case "one":
+                return `${v.count} Apfel`;
+            default:
+                return `${v.count} Äpfel`;
+        }
+    })({
+        count
+    }));
+};
+export const defaultLocale = ()=>{
+    console.log("Hello");
+};
+export const fixedLng = ()=>"Hallo";
+export async function awaitedLng() {
+    return "Hallo";
+}
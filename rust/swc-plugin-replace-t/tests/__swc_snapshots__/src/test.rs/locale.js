@@ -0,0 +1,13 @@
+export const locale = ({ count })=>{
+    console.log("Hallo");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("de").select(v.count)){
+            case "one":
+                return `${v.count} Apfel`;
+            default:
+                return `${v.count} Äpfel`;
+        }
+    })({
+        count
+    }));
+};
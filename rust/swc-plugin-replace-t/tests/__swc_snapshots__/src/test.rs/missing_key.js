@@ -0,0 +1,9 @@
+export const missingKey = ({ count })=>{
+    console.log("test1");
+    console.log("notExists");
+    console.log("Not exists");
+    return <>Not <b>exists</b></>;
+};
+export const missingKeyByNamespace = ()=>{
+    console.log("notExists");
+};
@@ -0,0 +1,12 @@
+import { useTranslation, Trans } from "react-i18next";
+export const translated = ()=>{
+    console.log("test1");
+};
+export const missingKeyRuntime = ({ count })=>{
+    const { t } = useTranslation('noArgs2');
+    console.log("test3");
+    console.log(t('notExists', {
+        count
+    }));
+    return <Trans i18nKey="notExists">Not exists</Trans>;
+};
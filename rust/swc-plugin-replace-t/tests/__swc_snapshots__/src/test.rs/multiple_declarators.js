@@ -0,0 +1,4 @@
+export const multiple = ()=>{
+    const x = 1;
+    console.log(x, "test1");
+};
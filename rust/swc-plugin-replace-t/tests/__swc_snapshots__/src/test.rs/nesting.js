@@ -0,0 +1,14 @@
+export const nesting = ({ user, girls, city })=>{
+    console.log("Welcome to Sandbox");
+    console.log("test1 and test2");
+    console.log(`Hello ${user}!`);
+    console.log(`${(()=>{
+        switch(new Intl.PluralRules("en").select(girls)){
+            case "one":
+                return `${girls} girl`;
+            default:
+                return `${girls} girls`;
+        }
+    })()} in ${city}`);
+    console.log("2 girls");
+};
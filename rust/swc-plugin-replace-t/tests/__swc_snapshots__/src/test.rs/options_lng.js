@@ -0,0 +1,15 @@
+export const lng = ({ count })=>{
+    console.log("Hallo");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("de").select(v.count)){
+            case "one":
+                return `${v.count} Apfel`;
+            default:
+                return `${v.count} Äpfel`;
+        }
+    })({
+        lng: 'de',
+        count
+    }));
+    console.log("Hello");
+};
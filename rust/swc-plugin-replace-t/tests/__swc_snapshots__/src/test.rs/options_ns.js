@@ -0,0 +1,10 @@
+export const ns = ()=>{
+    console.log("test3");
+    console.log(((v)=>`test4`)({
+        ns: [
+            'noArgs2',
+            'noArgs'
+        ]
+    }));
+    console.log("test1");
+};
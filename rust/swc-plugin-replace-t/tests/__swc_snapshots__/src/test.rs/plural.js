@@ -0,0 +1,39 @@
+export const plural = ({ n, options })=>{
+    console.log("1 item");
+    console.log("1 item");
+    console.log("1000 items");
+    console.log("2 items");
+    console.log("no messages");
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("en").select(v.count)){
+            case "one":
+                return `${v.count} item`;
+            default:
+                return `${v.count} items`;
+        }
+    })({
+        count: n
+    }));
+    console.log(((v)=>{
+        switch(v.count === 0 ? "zero" : new Intl.PluralRules("en").select(v.count)){
+            case "zero":
+                return `no messages`;
+            case "one":
+                return `one message`;
+            default:
+                return `${v.count} messages`;
+        }
+    })({
+        count: n
+    }));
+    console.log(((v)=>{
+        switch(v.count === 0 ? "zero" : new Intl.PluralRules("en").select(v.count)){
+            case "zero":
+                return `no messages`;
+            case "one":
+                return `one message`;
+            default:
+                return `${v.count} messages`;
+        }
+    })(options));
+};
@@ -0,0 +1,5 @@
+const { initReactI18next } = require("react-i18next");
+exports.init = ()=>i18next.use(initReactI18next);
+exports.message = ()=>{
+    return "test1";
+};
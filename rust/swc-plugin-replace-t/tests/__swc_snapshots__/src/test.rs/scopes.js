@@ -0,0 +1,16 @@
+export const First = ()=>{
+    const useInner = ()=>{
+        return [
+            "test1",
+            "test3"
+        ];
+    };
+    return <>{"line1\nline2"}</>;
+};
+export function Second() {
+    function Nested() {
+        return <>{"test2"}</>;
+    }
+    console.log("hi");
+    return <>{"hi!"}</>;
+}
@@ -0,0 +1,16 @@
+export const separators = ({ name, context })=>{
+    console.log("Welcome!");
+    console.log("Welcome!");
+    console.log(`Hello, ${name}!`);
+    console.log(((v)=>{
+        switch(v.context){
+            case "male":
+                return `A boyfriend.`;
+            default:
+                return `A friend.`;
+        }
+    })({
+        context
+    }));
+    console.log("test1");
+};
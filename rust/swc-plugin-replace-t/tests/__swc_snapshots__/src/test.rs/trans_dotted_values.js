@@ -0,0 +1,13 @@
+const ComponentWithDottedValues = ({ user, values })=>{
+    return <div>
+
+            <>{user.name} lives in {user.address.city}</>
+
+            <>{values.user.name} lives in {values.user.address.city}</>
+
+            <>{((values)=>"{{user.name}} lives in {{user.address.city}}")({
+        name: user.name
+    })}</>
+
+          </div>;
+};
@@ -0,0 +1,28 @@
+const ComponentWithI18nKey = ({ name, count, values })=>{
+    return <div>
+
+            <>{"hi"}</>
+
+            <><Link href="foo.com">aaaaa</Link><Button/></>
+
+            <>Hello <strong>{name}</strong>!</>
+
+            <>Hello <bold>{values.name}</bold>!</>
+
+            <>{((count)=>{
+        switch(new Intl.PluralRules("en").select(count)){
+            case "one":
+                return <>You have {count} item</>;
+            default:
+                return <>You have {count} items</>;
+        }
+    })(count)}</>
+
+            <>You have {1} item</>
+
+            <>{"test2"}</>
+
+            <>{"test3"}</>
+
+          </div>;
+};
@@ -0,0 +1,19 @@
+const ComponentWithTexts = ({ name })=>{
+    return <div>
+
+            <><b>bold</b>{"{fetch('//evil')}"}</>
+
+            <>{"1 < 2 "}<b>is</b> {name}</>
+
+            <>`<b>{name}</b>{"` costs ${price}"}</>
+
+            <><b>first</b> and <i>{name}</i></>
+
+            {<><b>bold</b>{"{fetch('//evil')}"}</>}
+
+            {"1 < 2"}
+
+            {<>`<b>{"{{name}}"}</b>{"` costs ${price}"}</>}
+
+          </div>;
+};
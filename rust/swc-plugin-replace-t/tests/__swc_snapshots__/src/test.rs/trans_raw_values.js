@@ -0,0 +1,3 @@
+const ComponentWithRawValues = ({ html })=>{
+    return <>{html} or {html}</>;
+};
@@ -0,0 +1,21 @@
+const ComponentWithValues = ({ user, getValues })=>{
+    return <div>
+
+            <>{((values)=><>Hello <bold>{values.name}</bold>!</>)(getValues())}</>
+
+            <>{((values)=><>Hello <bold>{values.name}</bold>!</>)({
+        name: user.name
+    })}</>
+
+            <>{((values)=><>Hello <bold>{values.name}</bold>!</>)({
+        ...user
+    })}</>
+
+            <>{((values)=><>Hello <bold>{"{{name}}"}</bold>!</>)({
+        title: user.title
+    })}</>
+
+            <>Hello <bold>{"{{name}}"}</bold>!</>
+
+          </div>;
+};
@@ -0,0 +1,12 @@
+export const locale = ()=>{
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("hi").select(v.count)){
+            case "one":
+                return `${v.count} सेब`;
+            default:
+                return `${v.count} सेब`;
+        }
+    })({
+        count: 0
+    }));
+};
@@ -0,0 +1,8 @@
+import { useTranslation, I18nextProvider, initReactI18next } from "react-i18next";
+i18next.use(initReactI18next);
+export const Provider = ({ children })=><I18nextProvider i18n={i18next}>{children}</I18nextProvider>;
+export const Component = ()=>{
+    const { i18n } = useTranslation('noArgs');
+    const [, instance] = useTranslation('noArgs2');
+    return <p lang={i18n.language} dir={instance.dir()}>{"test1"}{"test3"}</p>;
+};
@@ -0,0 +1,21 @@
+export const object = ({ min, max, n, values })=>{
+    console.log(`value: ${max} - ${min}`);
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)({
+        ...values,
+        min
+    }));
+    console.log(((v)=>`value: ${v.max} - ${v.min}`)(values));
+};
+const plural = ({ n })=>{
+    console.log(((v)=>{
+        switch(new Intl.PluralRules("en").select(v.count)){
+            case "one":
+                return `${v.count} item`;
+            default:
+                return `${v.count} items`;
+        }
+    })({
+        count: n,
+        defaultValue_other: '{{count}} items'
+    }));
+};
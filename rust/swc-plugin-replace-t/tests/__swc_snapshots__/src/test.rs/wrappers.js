@@ -0,0 +1,15 @@
+class Greeting extends React.Component {
+    render() {
+        return <p>{"test1"} {"line1\nline2"}</p>;
+    }
+}
+function Prefixed(props) {
+    return <p>{"test2"}</p>;
+}
+export const Render = ()=><div>
+
+            <p>{"test3"}</p>
+
+        </div>;
+export default Greeting;
+export const PrefixedWithTranslation = Prefixed;
@@ -0,0 +1,4 @@
+import { withTranslation, Translation } from "react-i18next";
+const Passed = ({ t })=><Child label={"test1"} t={t}/>;
+export const Render = ()=><Translation ns="noArgs2">{(t)=><Child label={"test3"} t={t}/>}</Translation>;
+export default withTranslation('noArgs')(Passed);